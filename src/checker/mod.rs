@@ -0,0 +1,873 @@
+use either::Either::{self, Left, Right};
+use std::collections::HashMap;
+
+use crate::error_handler::Error;
+use crate::lexer::token::Token;
+use crate::parser::{
+    ast::{
+        control_flow::SwitchNode,
+        functions::{ArgNode, DefineFunctionNode, FunctionNode, ScopeNode},
+        types::VariantNode,
+        variables::{
+            ArrElem, ArrFunc, ArrayVarType, MapFunc, SpreadNode, StrPart, ValueNode, VarType,
+        },
+        Nodes,
+    },
+    ty_to_string,
+};
+
+// What a value is checked against, only changes the diagnostic
+#[derive(Debug, Clone, Copy)]
+enum Target<'b, 'a> {
+    Var(&'b str),
+    Arg(&'b str, &'b DefineFunctionNode<'a>),
+    // Position of an argument in a call through a function value, which has no names
+    FuncValueArg(usize),
+    Payload(&'b VariantNode<'a>),
+    Return(&'b str),
+    // Element of an array literal used in an expression, which takes its type from the
+    // first element
+    Elem,
+}
+
+// An operand while checking an expression, `node` is its leftmost operand and is used to point
+// at it. Number literals, and arithmetic between them, also keep their value when it's known so
+// it can be checked against the type they end up with
+struct Operand<'b, 'a> {
+    ty: Option<Either<VarType, ArrayVarType>>,
+    node: &'b Nodes<'a>,
+    literal: Option<Option<i128>>,
+}
+
+// Runs after parsing and computes the type of every expression, reporting values that don't
+// match the type of the variable, argument or return they're used for
+#[derive(Debug, PartialEq, Clone)]
+pub struct Checker<'a> {
+    lines: Vec<String>,
+    error_handler: Error<'a>,
+    current_func: Option<DefineFunctionNode<'a>>,
+    // Interfaces implemented by each type
+    impls: HashMap<String, Vec<String>>,
+}
+
+impl<'a> Checker<'a> {
+    // * Main functions
+
+    pub fn new(
+        init_tok: Token<'a>,
+        file_name: String,
+        lines: Vec<String>,
+        impls: HashMap<String, Vec<String>>,
+    ) -> Self {
+        Self {
+            lines,
+            error_handler: Error::new(init_tok, "".to_owned(), file_name),
+            current_func: None,
+            impls,
+        }
+    }
+
+    pub fn check(&mut self, ast: &[Nodes<'a>]) {
+        for node in ast {
+            self.check_node(node);
+        }
+    }
+
+    fn update_error_handler(&mut self, node: &Nodes<'a>) {
+        if let Some(token) = node.get_token() {
+            self.error_handler.token = token;
+            self.error_handler.line_string = self.lines[token.line].clone();
+        }
+    }
+
+    // `found` can be used where `expected` is wanted, numbers fit types they widen to and fixed
+    // arrays fit unsized ones
+    fn fits(
+        found: &Option<Either<VarType, ArrayVarType>>,
+        expected: &Either<VarType, ArrayVarType>,
+    ) -> bool {
+        match (found, expected) {
+            (Some(Left(found)), Left(expected)) => found.widens_to(expected),
+            (Some(Right(found)), Right(expected)) => found.fits(expected),
+            _ => false,
+        }
+    }
+
+    // * Statements
+
+    fn check_node(&mut self, node: &Nodes<'a>) {
+        match node {
+            Nodes::VarNode(var) => self.check_value(Target::Var(&var.0), &var.1),
+            Nodes::DestructureNode(node) => self.check_value(Target::Var(&node.name()), &node.val),
+            Nodes::AssignToVarNode(node) => {
+                self.check_assign(Target::Var(&node.0 .0 .0), &node.1, &node.0 .0 .1 .1)
+            }
+            Nodes::AssignToVarArrNode(node) => {
+                let var: Nodes<'a> = Nodes::CallVarArrNode(node.0.clone());
+
+                if let Some(ty) = self.check_expr(&var) {
+                    self.check_assign(Target::Var(&node.0 .0 .0 .0), &node.1, &ty);
+                }
+            }
+            Nodes::AssignToMapNode(node) => {
+                let map: Nodes<'a> = Nodes::CallMapNode(node.0.clone());
+
+                if let Some(ty) = self.check_expr(&map) {
+                    self.check_assign(Target::Var(&node.0 .0 .0 .0), &node.1, &ty);
+                }
+            }
+            Nodes::ReturnNode(node) => {
+                let func: DefineFunctionNode<'a> = self.current_func.clone().unwrap();
+
+                if let Some(ret_val) = &node.ret_val {
+                    self.check_value(Target::Return(&func.name), ret_val);
+                }
+            }
+            Nodes::ReturnIfNode(node) => {
+                let func: DefineFunctionNode<'a> = self.current_func.clone().unwrap();
+
+                self.check_cond(&node.cond);
+
+                if let Some(ret_val) = &node.ret_val {
+                    self.check_value(Target::Return(&func.name), ret_val);
+                }
+            }
+            Nodes::BreakIfNode(node) => self.check_cond(&node.cond),
+            Nodes::FunctionNode(func) => self.check_function(func),
+            Nodes::TypeNode(ty) => {
+                for arg in &ty.args {
+                    if let Right(func) = &arg.val {
+                        self.check_function(func);
+                    }
+                }
+            }
+            Nodes::IfNode(node) => {
+                self.check_cond(&node.cond);
+                self.check_scope(&node.scope);
+
+                if let Some(else_scope) = &node.else_scope {
+                    self.check_scope(else_scope);
+                }
+            }
+            Nodes::WhileNode(node) => {
+                self.check_cond(&node.cond);
+                self.check_scope(&node.scope);
+            }
+            Nodes::DoWhileNode(node) => {
+                self.check_scope(&node.scope);
+                self.check_cond(&node.cond);
+            }
+            Nodes::ForNode(node) => {
+                self.check_expr(&node.iterable);
+
+                if let Some(step) = &node.step {
+                    self.check_step(step);
+                }
+
+                self.check_scope(&node.scope);
+            }
+            Nodes::LoopNode(node) => self.check_scope(&node.scope),
+            Nodes::SwitchNode(node) => {
+                self.check_expr(&node.val);
+
+                for scope in Self::switch_scopes(node) {
+                    self.check_scope(scope);
+                }
+            }
+            node => {
+                self.check_expr(node);
+            }
+        }
+    }
+
+    fn check_scope(&mut self, scope: &ScopeNode<'a>) {
+        for node in &scope.scope {
+            self.check_node(node);
+        }
+    }
+
+    fn check_function(&mut self, func: &FunctionNode<'a>) {
+        for arg in &func.define_node.args {
+            if let Some(default) = &arg.default {
+                self.check_assign(Target::Arg(&arg.name, &func.define_node), default, &arg.ty);
+            }
+        }
+
+        let outer_func: Option<DefineFunctionNode<'a>> =
+            self.current_func.replace(func.define_node.clone());
+
+        self.check_scope(&func.scope);
+
+        self.current_func = outer_func;
+
+        if let Some(ret_ty) = &func.define_node.ret_ty {
+            if !Self::always_returns(&func.scope.scope) {
+                self.error_handler.token = func.define_node.location.token;
+                self.error_handler.line_string = func.define_node.location.line_string.clone();
+                self.error_handler.throw_missing_return(
+                    &func.define_node.name,
+                    ty_to_string(&Some(ret_ty.clone())),
+                );
+            }
+        }
+    }
+
+    // Whether running `nodes` always ends in a `return`, a `loop` only ends through `break_if`
+    fn always_returns(nodes: &[Nodes<'a>]) -> bool {
+        nodes.iter().any(|node: &Nodes<'a>| match node {
+            Nodes::ReturnNode(_) => true,
+            Nodes::LoopNode(node) => !Self::can_break(&node.scope.scope, &[&node.label], false),
+            // The body runs at least once, unless it can go back to the condition
+            Nodes::DoWhileNode(node) => {
+                Self::always_returns(&node.scope.scope)
+                    && !Self::can_break(&node.scope.scope, &[&node.label], true)
+            }
+            // Switches always handle every variant
+            Nodes::SwitchNode(node) => Self::switch_scopes(node)
+                .all(|scope: &ScopeNode<'a>| Self::always_returns(&scope.scope)),
+            Nodes::IfNode(node) => {
+                Self::always_returns(&node.scope.scope)
+                    && node
+                        .else_scope
+                        .as_ref()
+                        .is_some_and(|else_scope: &ScopeNode<'a>| {
+                            Self::always_returns(&else_scope.scope)
+                        })
+            }
+            _ => false,
+        })
+    }
+
+    // Whether `nodes` can leave the first loop of `loops`, the labels of the loops from that
+    // one to the innermost around `nodes`. Breaks and continues leave every loop after the one
+    // they act on, `continues` also counts the ones acting on the first loop
+    fn can_break(nodes: &[Nodes<'a>], loops: &[&Option<String>], continues: bool) -> bool {
+        // Position in `loops` of the loop a jump acts on, `None` for loops around the first one
+        let target = |label: &Option<String>| match label {
+            Some(_) => loops.iter().position(|loop_label| *loop_label == label),
+            None => Some(loops.len() - 1),
+        };
+
+        nodes.iter().any(|node: &Nodes<'a>| match node {
+            Nodes::BreakNode(node) => target(&node.label).is_none_or(|i: usize| i == 0),
+            Nodes::BreakIfNode(node) => target(&node.label).is_none_or(|i: usize| i == 0),
+            Nodes::ContinueNode(node) => {
+                target(&node.label).is_none_or(|i: usize| i == 0 && continues)
+            }
+            Nodes::SwitchNode(node) => Self::switch_scopes(node)
+                .any(|scope: &ScopeNode<'a>| Self::can_break(&scope.scope, loops, continues)),
+            Nodes::IfNode(node) => {
+                Self::can_break(&node.scope.scope, loops, continues)
+                    || node
+                        .else_scope
+                        .as_ref()
+                        .is_some_and(|else_scope: &ScopeNode<'a>| {
+                            Self::can_break(&else_scope.scope, loops, continues)
+                        })
+            }
+            node => Self::loop_parts(node).is_some_and(|(label, scope)| {
+                Self::can_break(&scope.scope, &[loops, &[label]].concat(), continues)
+            }),
+        })
+    }
+
+    // Label and body of loop nodes
+    fn loop_parts<'b>(node: &'b Nodes<'a>) -> Option<(&'b Option<String>, &'b ScopeNode<'a>)> {
+        match node {
+            Nodes::WhileNode(node) => Some((&node.label, &node.scope)),
+            Nodes::DoWhileNode(node) => Some((&node.label, &node.scope)),
+            Nodes::ForNode(node) => Some((&node.label, &node.scope)),
+            Nodes::LoopNode(node) => Some((&node.label, &node.scope)),
+            _ => None,
+        }
+    }
+
+    fn switch_scopes<'b>(node: &'b SwitchNode<'a>) -> impl Iterator<Item = &'b ScopeNode<'a>> {
+        node.cases
+            .iter()
+            .map(|case| &case.scope)
+            .chain(&node.default)
+    }
+
+    fn check_cond(&mut self, cond: &Nodes<'a>) {
+        let cond_ty: Option<Either<VarType, ArrayVarType>> = self.check_expr(cond);
+
+        if cond_ty != Some(Left(VarType::Bool)) {
+            self.update_error_handler(cond);
+            self.error_handler
+                .throw_wrong_condition_type(ty_to_string(&cond_ty));
+        }
+    }
+
+    fn check_step(&mut self, step: &Nodes<'a>) {
+        let step_ty: Option<Either<VarType, ArrayVarType>> = self.check_expr(step);
+
+        if !matches!(step_ty, Some(Left(VarType::Int { ty: _ }))) {
+            self.update_error_handler(step);
+            self.error_handler
+                .throw_wrong_step_type(ty_to_string(&step_ty));
+        }
+
+        if let Some(step_val) = step.const_eval().filter(|&step_val: &i128| step_val <= 0) {
+            self.update_error_handler(step);
+            self.error_handler.throw_non_positive_step(step_val);
+        }
+    }
+
+    // * Values
+
+    // `value.1` is the type the value was parsed for
+    fn check_value(&mut self, target: Target<'_, 'a>, value: &ValueNode<'a>) {
+        match &value.0 {
+            Left(node) => self.check_assign(target, node, &value.1),
+            Right(elems) => {
+                for elem in elems {
+                    self.check_elem(target, &elem.0, &value.1);
+                }
+            }
+        }
+    }
+
+    fn check_elem(
+        &mut self,
+        target: Target<'_, 'a>,
+        elem: &Nodes<'a>,
+        arr_ty: &Either<VarType, ArrayVarType>,
+    ) {
+        // Array literals are only parsed for array types
+        let elem_ty: Either<VarType, ArrayVarType> = arr_ty.clone().unwrap_right().elem_ty();
+
+        match elem {
+            Nodes::SpreadNode(spread) => self.check_spread(spread, &elem_ty),
+            elem => self.check_assign(target, elem, &elem_ty),
+        }
+    }
+
+    // The spread array has to hold values that fit `elem_ty`
+    fn check_spread(&mut self, spread: &SpreadNode<'a>, elem_ty: &Either<VarType, ArrayVarType>) {
+        let val_ty: Option<Either<VarType, ArrayVarType>> = self.check_expr(&spread.val);
+
+        if !matches!(&val_ty, Some(Right(arr_ty)) if Self::fits(&Some(arr_ty.elem_ty()), elem_ty)) {
+            self.update_error_handler(&spread.val);
+            self.error_handler.throw_wrong_spread_type(
+                ty_to_string(&val_ty),
+                ty_to_string(&Some(elem_ty.clone())),
+            );
+        }
+    }
+
+    fn check_assign(
+        &mut self,
+        target: Target<'_, 'a>,
+        val: &Nodes<'a>,
+        expected: &Either<VarType, ArrayVarType>,
+    ) {
+        // Literals nested in other literals aren't always wrapped in a `ValueNode`
+        match val {
+            // Array literals used in expressions have an inferred type, their elements are
+            // checked against the expected one instead so number literals can take it
+            Nodes::ValueNode(value) => {
+                let len: Option<isize> = match &value.0 {
+                    Left(node) => match node.as_ref() {
+                        Nodes::ArrRepeatNode(node) => Some(node.count),
+                        node => return self.check_assign(target, node, expected),
+                    },
+                    Right(elems) => ArrElem::literal_len(elems),
+                };
+
+                if let Right(arr_ty) = expected {
+                    if arr_ty
+                        .get_init_num()
+                        .is_none_or(|init_num: isize| len == Some(init_num))
+                    {
+                        let value: ValueNode<'a> = ValueNode(value.0.clone(), expected.clone());
+
+                        return self.check_value(target, &value);
+                    }
+                }
+            }
+            Nodes::ArrRepeatNode(node) => return self.check_elem(target, &node.val, expected),
+            // Ends of ranges are checked on their own, so number literals can take the type of
+            // the range, `let r: range<u8> = 0...10;`
+            Nodes::ProcessedMathNode(math) => {
+                if let (Some((lhs, rhs)), Left(VarType::Range { ty })) =
+                    (math.range_ends(), expected)
+                {
+                    let int_ty: Either<VarType, ArrayVarType> = Left(VarType::Int { ty: *ty });
+
+                    self.check_assign(target, &lhs, &int_ty);
+                    self.check_assign(target, &rhs, &int_ty);
+
+                    return;
+                }
+            }
+            Nodes::MapNode(map) => {
+                if let Left(VarType::Map { key, val }) = expected {
+                    for (entry_key, entry_val) in &map.entries {
+                        self.check_assign(target, entry_key, key);
+                        self.check_assign(target, entry_val, val);
+                    }
+
+                    return;
+                }
+            }
+            // Elements of tuple literals are checked one by one, so number literals can take
+            // the type of their element
+            Nodes::TupleNode(tuple) => {
+                if let Left(VarType::Tuple { elems }) = expected {
+                    if tuple.elems.len() == elems.len() {
+                        for (elem, elem_ty) in tuple.elems.iter().zip(elems) {
+                            self.check_assign(target, elem, elem_ty);
+                        }
+
+                        return;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        let val_ty: Option<Either<VarType, ArrayVarType>> = self.check_expr(val);
+
+        // Number literals take the expected type, even when it's already their default one
+        if let (Some(Left(val_ty)), Left(expected)) = (&val_ty, expected) {
+            if val.is_num_literal() && val_ty.literal_fits(expected) {
+                return self.check_literal(val, val.const_eval(), expected);
+            }
+        }
+
+        if Self::fits(&val_ty, expected) {
+            return;
+        }
+
+        // Values of a type fit the interfaces it implements
+        if let (
+            Some(Left(VarType::Type { name, args: _ })),
+            Left(VarType::Type {
+                name: interface,
+                args: _,
+            }),
+        ) = (&val_ty, expected)
+        {
+            if self
+                .impls
+                .get(name)
+                .is_some_and(|impls: &Vec<String>| impls.contains(interface))
+            {
+                return;
+            }
+        }
+
+        if let (Some(Left(val_ty)), Left(expected)) = (&val_ty, expected) {
+            if val_ty.is_num() && expected.is_num() {
+                self.update_error_handler(val);
+                self.error_handler
+                    .throw_lossy_conversion(val_ty.to_string(), expected.to_string());
+            }
+        }
+
+        let expected: String = ty_to_string(&Some(expected.clone()));
+
+        self.update_error_handler(val);
+
+        match target {
+            Target::Var(name) => {
+                self.error_handler
+                    .throw_wrong_assign_type(name, ty_to_string(&val_ty), expected)
+            }
+            Target::Arg(name, func) => self.error_handler.throw_wrong_arg_type(
+                name,
+                ty_to_string(&val_ty),
+                expected,
+                &func.name,
+                &func.location,
+            ),
+            Target::FuncValueArg(pos) => self.error_handler.throw_wrong_func_value_arg_type(
+                pos,
+                ty_to_string(&val_ty),
+                expected,
+            ),
+            Target::Return(name) => {
+                self.error_handler
+                    .throw_wrong_return_type(name, ty_to_string(&val_ty), expected)
+            }
+            Target::Payload(variant) => self.error_handler.throw_wrong_payload_type(
+                &variant.name,
+                ty_to_string(&val_ty),
+                expected,
+                &variant.location,
+            ),
+            Target::Elem => self
+                .error_handler
+                .throw_wrong_elem_type(ty_to_string(&val_ty), expected),
+        }
+    }
+
+    // `val` is the value of an integer literal used as `ty`, when it's known
+    fn check_literal(&mut self, node: &Nodes<'a>, val: Option<i128>, ty: &VarType) {
+        if let (VarType::Int { ty: int_ty }, Some(val)) = (ty, val) {
+            if !int_ty.contains(val) {
+                self.update_error_handler(node);
+                self.error_handler
+                    .throw_literal_out_of_range(val, ty.to_string());
+            }
+        }
+    }
+
+    // * Expressions
+
+    // Checks the operands of the expression and returns its type
+    fn check_expr(&mut self, node: &Nodes<'a>) -> Option<Either<VarType, ArrayVarType>> {
+        match node {
+            Nodes::ProcessedMathNode(math) => {
+                let mut stack: Vec<Operand<'_, 'a>> = vec![];
+
+                for node in &math.out_stream {
+                    match node {
+                        Nodes::MathOpTypeNode(op) => {
+                            let rhs: Operand<'_, 'a> = stack.pop().unwrap();
+                            let lhs: Operand<'_, 'a> = stack.pop().unwrap();
+
+                            let ty: Option<VarType> = match (&lhs.ty, &rhs.ty) {
+                                (Some(Left(lhs_ty)), Some(Left(rhs_ty))) => op.literal_result_ty(
+                                    (lhs_ty, lhs.literal.is_some()),
+                                    (rhs_ty, rhs.literal.is_some()),
+                                ),
+                                _ => None,
+                            };
+
+                            let ty: VarType = match ty {
+                                Some(ty) => ty,
+                                None => {
+                                    self.update_error_handler(lhs.node);
+                                    self.error_handler.throw_wrong_operand_types(
+                                        op.op.to_string(),
+                                        ty_to_string(&lhs.ty),
+                                        ty_to_string(&rhs.ty),
+                                    );
+
+                                    unreachable!()
+                                }
+                            };
+
+                            // Literals that took the type of the other operand have to fit it
+                            for (operand, other) in [(&lhs, &rhs), (&rhs, &lhs)] {
+                                if let (
+                                    Some(val),
+                                    None,
+                                    Some(Left(operand_ty)),
+                                    Some(Left(other_ty)),
+                                ) = (operand.literal, other.literal, &operand.ty, &other.ty)
+                                {
+                                    if operand_ty.literal_fits(other_ty) {
+                                        self.check_literal(operand.node, val, other_ty);
+                                    }
+                                }
+                            }
+
+                            let literal: Option<Option<i128>> = match (lhs.literal, rhs.literal) {
+                                (Some(lhs), Some(rhs)) if op.is_arithmetic() => {
+                                    Some(lhs.zip(rhs).and_then(|(lhs, rhs)| op.eval(lhs, rhs)))
+                                }
+                                _ => None,
+                            };
+
+                            stack.push(Operand {
+                                ty: Some(Left(ty)),
+                                node: lhs.node,
+                                literal,
+                            });
+                        }
+                        _ => {
+                            let ty: Option<Either<VarType, ArrayVarType>> = self.check_expr(node);
+
+                            stack.push(Operand {
+                                ty,
+                                node,
+                                literal: node.is_num_literal().then(|| node.const_eval()),
+                            });
+                        }
+                    }
+                }
+
+                stack.pop().unwrap().ty
+            }
+            Nodes::TupleNode(tuple) => {
+                for elem in &tuple.elems {
+                    self.check_expr(elem);
+                }
+
+                node.get_ty()
+            }
+            Nodes::CastNode(cast) => {
+                let val_ty: Option<Either<VarType, ArrayVarType>> = self.check_expr(&cast.val);
+
+                if !matches!(&val_ty, Some(Left(val_ty)) if val_ty.can_cast_to(&cast.ty)) {
+                    self.error_handler.token = cast.token;
+                    self.error_handler.line_string = self.lines[cast.token.line].clone();
+                    self.error_handler
+                        .throw_invalid_cast(ty_to_string(&val_ty), cast.ty.to_string());
+                }
+
+                node.get_ty()
+            }
+            Nodes::CallMethodNode(call) => self.check_expr(&Nodes::CallFuncNode(call.clone())),
+            Nodes::OpMethodNode(op) => {
+                self.check_expr(&Nodes::CallFuncNode(op.call.clone()));
+
+                node.get_ty()
+            }
+            Nodes::CallFuncNode(call) => {
+                let func: &DefineFunctionNode<'a> = &call.define_node;
+
+                // Named arguments give the rest in any order once the positional ones end
+                let positional: usize = call
+                    .args
+                    .iter()
+                    .take_while(|arg: &&Nodes<'a>| !matches!(arg, Nodes::NamedArgNode(_)))
+                    .count();
+                let mut given: Vec<bool> = (0..func.args.len()).map(|i| i < positional).collect();
+
+                for arg in &call.args[positional..] {
+                    self.update_error_handler(arg);
+
+                    let Nodes::NamedArgNode(arg) = arg else {
+                        self.error_handler.throw_positional_after_named();
+
+                        unreachable!()
+                    };
+
+                    match func.arg_index(&arg.name) {
+                        Some(idx) if given[idx] => {
+                            self.error_handler.throw_arg_given_twice(&arg.name)
+                        }
+                        Some(idx) => given[idx] = true,
+                        None => self.error_handler.throw_unknown_arg(
+                            &arg.name,
+                            &func.name,
+                            &func.location,
+                        ),
+                    }
+                }
+
+                if !func.variadic && positional > func.args.len() {
+                    // Extra arguments are pointed at directly, missing ones at the call
+                    self.update_error_handler(&call.args[func.args.len()]);
+                    self.error_handler.throw_wrong_arg_count(
+                        &func.name,
+                        func.args.len(),
+                        call.args.len(),
+                        &func.location,
+                    );
+                }
+
+                let missing: Option<&ArgNode<'a>> = func.args[..func.fixed_args()]
+                    .iter()
+                    .zip(&given)
+                    .find(|(arg, given)| !**given && arg.default.is_none())
+                    .map(|(arg, _)| arg);
+
+                if let Some(arg) = missing {
+                    self.update_error_handler(node);
+
+                    // Calls that can only give arguments in order just say how many they gave
+                    if positional < call.args.len()
+                        || func.args.iter().any(|arg| arg.default.is_some())
+                    {
+                        self.error_handler
+                            .throw_missing_arg(&arg.name, &func.name, &func.location);
+                    } else if func.variadic {
+                        self.error_handler.throw_too_few_args(
+                            &func.name,
+                            func.fixed_args(),
+                            call.args.len(),
+                            &func.location,
+                        );
+                    } else {
+                        self.error_handler.throw_wrong_arg_count(
+                            &func.name,
+                            func.args.len(),
+                            call.args.len(),
+                            &func.location,
+                        );
+                    }
+                }
+
+                for (i, arg) in call.args.iter().enumerate() {
+                    match arg {
+                        Nodes::NamedArgNode(arg) => {
+                            let arg_node: &ArgNode<'a> =
+                                &func.args[func.arg_index(&arg.name).unwrap()];
+
+                            self.check_assign(
+                                Target::Arg(&arg_node.name, func),
+                                &arg.val,
+                                &arg_node.ty,
+                            );
+                        }
+                        Nodes::SpreadNode(spread) if i >= func.fixed_args() => {
+                            self.check_spread(spread, &func.arg_at(i).unwrap().1)
+                        }
+                        arg => {
+                            let (arg_node, ty) = func.arg_at(i).unwrap();
+
+                            self.check_assign(Target::Arg(&arg_node.name, func), arg, &ty);
+                        }
+                    }
+                }
+
+                node.get_ty()
+            }
+            Nodes::LambdaNode(lambda) => {
+                self.check_function(&lambda.func);
+
+                node.get_ty()
+            }
+            Nodes::CallFuncValueNode(call) => {
+                // Only values of function types are parsed as callees
+                let callee_ty: Option<Either<VarType, ArrayVarType>> =
+                    self.check_expr(&call.callee);
+                let args: Vec<Either<VarType, ArrayVarType>> = match &callee_ty {
+                    Some(Left(VarType::Func { args, ret_ty: _ })) => args.clone(),
+                    _ => unreachable!(),
+                };
+
+                if call.args.len() != args.len() {
+                    match call.args.get(args.len()) {
+                        Some(arg) => self.update_error_handler(arg),
+                        None => self.update_error_handler(node),
+                    }
+
+                    self.error_handler.throw_wrong_func_value_arg_count(
+                        ty_to_string(&callee_ty),
+                        args.len(),
+                        call.args.len(),
+                    );
+                }
+
+                for (i, (arg, ty)) in call.args.iter().zip(&args).enumerate() {
+                    self.check_assign(Target::FuncValueArg(i + 1), arg, ty);
+                }
+
+                node.get_ty()
+            }
+            Nodes::CallVarArrNode(var) => {
+                for index in &var.1 {
+                    self.check_expr(&index.index);
+                }
+
+                node.get_ty()
+            }
+            Nodes::CallArrFuncNode(var) => {
+                if let ArrFunc::Push(val) = &var.1 {
+                    let elem_ty: Either<VarType, ArrayVarType> =
+                        var.0 .0 .1 .1.clone().unwrap_right().elem_ty();
+
+                    self.check_assign(Target::Var(&var.0 .0 .0), val, &elem_ty);
+                }
+
+                node.get_ty()
+            }
+            Nodes::CallMapNode(var) => {
+                if let Left(VarType::Map { key, val: _ }) = &var.0 .0 .1 .1 {
+                    self.check_assign(Target::Var(&var.0 .0 .0), &var.1, key);
+                }
+
+                node.get_ty()
+            }
+            Nodes::CallMapFuncNode(var) => {
+                if let (MapFunc::Remove(key_val), Left(VarType::Map { key, val: _ })) =
+                    (&var.1, &var.0 .0 .1 .1)
+                {
+                    self.check_assign(Target::Var(&var.0 .0 .0), key_val, key);
+                }
+
+                node.get_ty()
+            }
+            Nodes::CallStrNode(var) => {
+                self.check_expr(&var.1);
+
+                node.get_ty()
+            }
+            Nodes::InterpolationNode(interpolation) => {
+                for part in &interpolation.parts {
+                    let StrPart::Expr(expr) = part else {
+                        continue;
+                    };
+
+                    let ty: Option<Either<VarType, ArrayVarType>> = self.check_expr(expr);
+
+                    if !matches!(
+                        ty,
+                        Some(Left(
+                            VarType::Int { ty: _ }
+                                | VarType::Float { ty: _ }
+                                | VarType::String
+                                | VarType::Char
+                                | VarType::Bool
+                        ))
+                    ) {
+                        self.update_error_handler(expr);
+                        self.error_handler.throw_cant_interpolate(ty_to_string(&ty));
+                    }
+                }
+
+                node.get_ty()
+            }
+            Nodes::CallFieldNode(field) => {
+                self.check_expr(&field.0);
+
+                node.get_ty()
+            }
+            Nodes::InitEnumNode(init) => {
+                let variant: &VariantNode<'a> = &init.variant;
+
+                if init.args.len() != variant.payload.len() {
+                    match init.args.get(variant.payload.len()) {
+                        Some(arg) => self.update_error_handler(arg),
+                        None => self.update_error_handler(node),
+                    }
+
+                    self.error_handler.throw_wrong_payload_count(
+                        &variant.name,
+                        variant.payload.len(),
+                        init.args.len(),
+                        &variant.location,
+                    );
+                }
+
+                for (arg, ty) in init.args.iter().zip(&variant.payload) {
+                    self.check_assign(Target::Payload(variant), arg, ty);
+                }
+
+                node.get_ty()
+            }
+            Nodes::InitTypeNode(init) => {
+                for (name, value) in &init.fields {
+                    self.check_value(Target::Var(name), value);
+                }
+
+                node.get_ty()
+            }
+            Nodes::ValueNode(value) => {
+                self.check_value(Target::Elem, value);
+
+                node.get_ty()
+            }
+            Nodes::NamedArgNode(_) => {
+                self.update_error_handler(node);
+                self.error_handler.throw_misplaced_named_arg();
+
+                None
+            }
+            // Spreads in array literals and variadic arguments are checked where they're used
+            Nodes::SpreadNode(_) => {
+                self.update_error_handler(node);
+                self.error_handler.throw_misplaced_spread();
+
+                None
+            }
+            node => node.get_ty(),
+        }
+    }
+}
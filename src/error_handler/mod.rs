@@ -4,13 +4,21 @@ use std::process::exit;
 
 use crate::lexer::token::Token;
 
+// Where something was declared, so errors found somewhere else can point back at it
+#[derive(Debug, PartialEq, Clone)]
+pub struct Location<'a> {
+    pub token: Token<'a>,
+    pub line_string: String,
+    pub file_name: String,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Error<'a> {
     pub token: Token<'a>,
     e_str: ColoredString,
     pub line_string: String,
     file_name: String,
-    using_local_scope: bool,
+    pub using_local_scope: bool,
 }
 
 impl<'a> Error<'a> {
@@ -24,25 +32,46 @@ impl<'a> Error<'a> {
         }
     }
 
+    pub fn location(&self) -> Location<'a> {
+        Location {
+            token: self.token,
+            line_string: self.line_string.clone(),
+            file_name: self.file_name.clone(),
+        }
+    }
+
     pub fn print(&self, offset: usize) {
-        let error_counter: String = "^".repeat(self.token.slice.len());
-        println!("{} file: {}", "-".blue(), self.file_name);
+        Self::print_location(&self.location(), offset);
+    }
+
+    fn print_location(location: &Location, offset: usize) {
+        let error_counter: String = "^".repeat(location.token.slice.len());
+        println!("{} file: {}", "-".blue(), location.file_name);
         println!("{}", "|".blue());
         println!(
             "{} {}.   {}",
             "|".blue(),
-            self.token.line + 1,
-            self.line_string
+            location.token.line + 1,
+            location.line_string
         );
         println!(
             "{}      {}{}",
             "|".blue(),
-            " ".repeat(self.token.column + offset),
+            " ".repeat(location.token.column + offset),
             error_counter.yellow()
         );
         println!("{}", "-".blue());
     }
 
+    fn print_declaration(&self, name: &str, location: &Location) {
+        println!(
+            "{}: \"{}\" is declared here",
+            "note".blue().bold(),
+            name.magenta().italic()
+        );
+        Self::print_location(location, 0);
+    }
+
     // General errors
     pub fn throw_name_already_used(&self, e_type: u8) {
         println!(
@@ -52,6 +81,8 @@ impl<'a> Error<'a> {
                 0 => "variable",
                 1 => "function",
                 2 => "type",
+                3 => "method",
+                4 => "type parameter",
                 _ => todo!(),
             }
             .magenta()
@@ -70,6 +101,7 @@ impl<'a> Error<'a> {
                 0 => "variable",
                 1 => "function",
                 2 => "type",
+                3 => "item",
                 _ => "",
             }
             .magenta()
@@ -101,6 +133,16 @@ impl<'a> Error<'a> {
         exit(0)
     }
 
+    pub fn throw_cant_mutate_var(&self, var_name: &str) {
+        println!(
+            "{}: cannot mutate immutable variable \"{}\"",
+            self.e_str,
+            var_name.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
     pub fn throw_cant_start_var_num(&self) {
         println!(
             "{}: cannot start variable name \"{}\" with number",
@@ -111,15 +153,88 @@ impl<'a> Error<'a> {
         exit(0)
     }
 
+    pub fn throw_wrong_destructure(&self, val_type: String, count: usize) {
+        println!(
+            "{}: cannot destructure value of type \"{}\" into {} variables",
+            self.e_str,
+            val_type.magenta().italic(),
+            count
+        );
+        self.print(0);
+        exit(0)
+    }
+
     // Array errors
 
-    pub fn throw_array_out_of_bounds(&self, arr_len: &isize) {
-        println!("{}: expected an array of size {}", self.e_str, arr_len,);
+    pub fn throw_wrong_array_size(&self, arr_len: isize, found: usize) {
+        println!(
+            "{}: expected an array of size {}, found {} elements",
+            self.e_str, arr_len, found
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_array_size_not_literal(&self, found: &str) {
+        println!(
+            "{}: array sizes must be integer literals, found \"{}\"",
+            self.e_str,
+            found.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_array_size_too_large(&self, size: &str) {
+        println!(
+            "{}: array size \"{}\" is too large",
+            self.e_str,
+            size.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_negative_array_size(&self, size: &str) {
+        println!(
+            "{}: array sizes can't be negative, found \"{}\"",
+            self.e_str,
+            size.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_no_default_value(&self, ty: String) {
+        println!(
+            "{}: values of type \"{}\" don't have a default value, \"[]\" can't be used",
+            self.e_str,
+            ty.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_cant_infer_type(&self, name: &str) {
+        println!(
+            "{}: can't infer the type of {}, add a type annotation",
+            self.e_str,
+            name.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_non_const_repeat_count(&self) {
+        println!(
+            "{}: the number of repetitions must be a constant non-negative integer",
+            self.e_str
+        );
         self.print(0);
         exit(0)
     }
 
-    pub fn throw_cant_use_num_array(&self, arr_name: &str, idx: isize) {
+    pub fn throw_cant_use_num_array(&self, arr_name: &str, idx: i128) {
         println!(
             "{}: can't access index {} in {}",
             self.e_str,
@@ -140,8 +255,168 @@ impl<'a> Error<'a> {
         exit(0)
     }
 
+    pub fn throw_not_an_array(&self, var_name: &str) {
+        println!(
+            "{}: \"{}\" is not an array",
+            self.e_str,
+            var_name.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+    pub fn throw_cant_resize_array(&self, arr_name: &str) {
+        println!(
+            "{}: can't resize \"{}\" because it has a fixed size",
+            self.e_str,
+            arr_name.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_unkown_array_func(&self) {
+        println!(
+            "{}: unknown array function: \"{}\"",
+            self.e_str,
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_unkown_map_func(&self) {
+        println!(
+            "{}: unknown map function: \"{}\"",
+            self.e_str,
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_unkown_str_func(&self) {
+        println!(
+            "{}: unknown string function: \"{}\"",
+            self.e_str,
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_str_index(&self, val: String) {
+        println!(
+            "{}: can't index a string with a value of type \"{}\", only integers and ranges can",
+            self.e_str,
+            val.magenta().italic(),
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_cant_assign_to_str_index(&self) {
+        println!(
+            "{}: strings can't be changed through an index, build a new one instead",
+            self.e_str,
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_unclosed_interpolation(&self) {
+        println!(
+            "{}: unmatched brace in string, write {} or {} for the brace itself",
+            self.e_str,
+            "{{".magenta().italic(),
+            "}}".magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_empty_interpolation(&self) {
+        println!(
+            "{}: expected an expression between the braces of the interpolation",
+            self.e_str,
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_cant_interpolate(&self, val: String) {
+        println!(
+            "{}: can't put a value of type \"{}\" in a string, only numbers, strings, chars and bools can",
+            self.e_str,
+            val.magenta().italic(),
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_duplicate_key(&self) {
+        println!(
+            "{}: key {} is already in the map literal",
+            self.e_str,
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_unsized_spread(&self, arr_len: isize) {
+        println!(
+            "{}: expected an array of size {}, found a spread of an array of unknown size",
+            self.e_str, arr_len
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_spread_type(&self, val_type: String, elem_type: String) {
+        println!(
+            "{}: cannot spread value of type \"{}\", expected an array of \"{}\"",
+            self.e_str,
+            val_type.magenta().italic(),
+            elem_type.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_elem_type(&self, val_type: String, elem_type: String) {
+        println!(
+            "{}: array literal of \"{}\" can't hold a value of type \"{}\"",
+            self.e_str,
+            elem_type.magenta().italic(),
+            val_type.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_misplaced_spread(&self) {
+        println!(
+            "{}: \"{}\" can only be used in array literals and for variadic arguments",
+            self.e_str,
+            "...".magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
     // Functions errors
 
+    pub fn throw_no_field(&self, ty: String) {
+        println!(
+            "{}: values of type \"{}\" don't have a field named \"{}\"",
+            self.e_str,
+            ty.magenta().italic(),
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
     pub fn throw_arg_alreay_used(&self, arg_name: String) {
         println!(
             "{}: argument name: \"{}\" already used",
@@ -162,6 +437,723 @@ impl<'a> Error<'a> {
         exit(0)
     }
 
+    pub fn throw_missing_return_value(&self, func_name: &str, ret_type: String) {
+        println!(
+            "{}: function \"{}\" must return a value of type \"{}\"",
+            self.e_str,
+            func_name.magenta().italic(),
+            ret_type.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_missing_return(&self, func_name: &str, ret_type: String) {
+        println!(
+            "{}: function \"{}\" doesn't return a value of type \"{}\" on every path",
+            self.e_str,
+            func_name.magenta().italic(),
+            ret_type.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_return_type(&self, func_name: &str, val_type: String, ret_type: String) {
+        println!(
+            "{}: cannot return value of type \"{}\" from function \"{}\" which returns \"{}\"",
+            self.e_str,
+            val_type.magenta().italic(),
+            func_name.magenta().italic(),
+            ret_type.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_arg_type(
+        &self,
+        arg_name: &str,
+        val_type: String,
+        arg_type: String,
+        func_name: &str,
+        func_location: &Location,
+    ) {
+        println!(
+            "{}: cannot pass value of type \"{}\" to argument \"{}\" which is of type \"{}\"",
+            self.e_str,
+            val_type.magenta().italic(),
+            arg_name.magenta().italic(),
+            arg_type.magenta().italic()
+        );
+        self.print(0);
+        self.print_declaration(func_name, func_location);
+        exit(0)
+    }
+
+    pub fn throw_wrong_arg_count(
+        &self,
+        func_name: &str,
+        expected: usize,
+        found: usize,
+        func_location: &Location,
+    ) {
+        println!(
+            "{}: function \"{}\" takes {} argument{} but {} {} given",
+            self.e_str,
+            func_name.magenta().italic(),
+            expected,
+            if expected == 1 { "" } else { "s" },
+            found,
+            if found == 1 { "was" } else { "were" }
+        );
+        self.print(0);
+        self.print_declaration(func_name, func_location);
+        exit(0)
+    }
+
+    pub fn throw_invalid_pipeline_stage(&self) {
+        println!(
+            "{}: \"{}\" is not a function, pipeline stages must be functions like \"{}\" or \"{}\"",
+            self.e_str,
+            self.token.slice.magenta().italic(),
+            "x | f".magenta().italic(),
+            "x | f(y)".magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_op_after_pipeline(&self) {
+        println!(
+            "{}: \"{}\" can't follow a pipeline, wrap it in parentheses to use its result: \"{}\"",
+            self.e_str,
+            self.token.slice.magenta().italic(),
+            "(x | f) + 1".magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_not_callable(&self, val_type: String) {
+        println!(
+            "{}: cannot call value of type \"{}\", only functions can be called",
+            self.e_str,
+            val_type.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_func_value_arg_type(&self, pos: usize, val_type: String, arg_type: String) {
+        println!(
+            "{}: cannot pass value of type \"{}\" as argument {} which is of type \"{}\"",
+            self.e_str,
+            val_type.magenta().italic(),
+            pos,
+            arg_type.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_func_value_arg_count(
+        &self,
+        func_type: String,
+        expected: usize,
+        found: usize,
+    ) {
+        println!(
+            "{}: function of type \"{}\" takes {} argument{} but {} {} given",
+            self.e_str,
+            func_type.magenta().italic(),
+            expected,
+            if expected == 1 { "" } else { "s" },
+            found,
+            if found == 1 { "was" } else { "were" }
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_too_few_args(
+        &self,
+        func_name: &str,
+        expected: usize,
+        found: usize,
+        func_location: &Location,
+    ) {
+        println!(
+            "{}: function \"{}\" takes at least {} argument{} but {} {} given",
+            self.e_str,
+            func_name.magenta().italic(),
+            expected,
+            if expected == 1 { "" } else { "s" },
+            found,
+            if found == 1 { "was" } else { "were" }
+        );
+        self.print(0);
+        self.print_declaration(func_name, func_location);
+        exit(0)
+    }
+
+    pub fn throw_missing_default(&self, arg_name: &str) {
+        println!(
+            "{}: argument \"{}\" needs a default value, it comes after arguments that have one",
+            self.e_str,
+            arg_name.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_default_in_lambda(&self) {
+        println!(
+            "{}: lambdas can't have default values, function types don't keep them",
+            self.e_str
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_missing_arg(&self, arg_name: &str, func_name: &str, func_location: &Location) {
+        println!(
+            "{}: missing value for argument \"{}\" of function \"{}\"",
+            self.e_str,
+            arg_name.magenta().italic(),
+            func_name.magenta().italic()
+        );
+        self.print(0);
+        self.print_declaration(func_name, func_location);
+        exit(0)
+    }
+
+    pub fn throw_unknown_arg(&self, arg_name: &str, func_name: &str, func_location: &Location) {
+        println!(
+            "{}: function \"{}\" doesn't have an argument named \"{}\"",
+            self.e_str,
+            func_name.magenta().italic(),
+            arg_name.magenta().italic()
+        );
+        self.print(0);
+        self.print_declaration(func_name, func_location);
+        exit(0)
+    }
+
+    pub fn throw_arg_given_twice(&self, arg_name: &str) {
+        println!(
+            "{}: argument \"{}\" is given more than once",
+            self.e_str,
+            arg_name.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_positional_after_named(&self) {
+        println!(
+            "{}: positional arguments can't come after named ones",
+            self.e_str
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_misplaced_named_arg(&self) {
+        println!(
+            "{}: named arguments can only be used when calling a function by its name",
+            self.e_str
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_variadic_not_last(&self, arg_name: &str) {
+        println!(
+            "{}: variadic argument \"{}\" must be the last argument",
+            self.e_str,
+            arg_name.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_variadic_lambda(&self) {
+        println!(
+            "{}: lambdas can't have variadic arguments, take an array instead",
+            self.e_str
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    // Control flow errors
+    pub fn throw_break_outside_loop(&self) {
+        println!(
+            "{}: \"{}\" can only be used inside a loop",
+            self.e_str,
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_unknown_label(&self) {
+        println!(
+            "{}: no loop around here is labeled \"{}\"",
+            self.e_str,
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_label_already_used(&self) {
+        println!(
+            "{}: label \"{}\" is already used by a loop around this one",
+            self.e_str,
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_label_not_on_loop(&self) {
+        println!(
+            "{}: labels can only be put on loops, found \"{}\"",
+            self.e_str,
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_not_iterable(&self, ty: String) {
+        println!(
+            "{}: can't iterate over a value of type \"{}\", only ranges, arrays and maps can be used in \"{}\"",
+            self.e_str,
+            ty.magenta().italic(),
+            "for".magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_range_index(&self) {
+        println!(
+            "{}: ranges have no indexes, their values already are: \"{}\"",
+            self.e_str,
+            "for i in 0..<n".magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_step_type(&self, ty: String) {
+        println!(
+            "{}: steps must be integers, found \"{}\"",
+            self.e_str,
+            ty.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_non_positive_step(&self, step: i128) {
+        println!(
+            "{}: steps must be positive, found \"{}\", descending ranges go from the higher end: \"{}\"",
+            self.e_str,
+            step.to_string().magenta().italic(),
+            "10...0".magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    // Type errors
+    pub fn throw_wrong_key_type(&self, ty: String) {
+        println!(
+            "{}: map keys can be integers, strings, chars or bools, found \"{}\"",
+            self.e_str,
+            ty.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_short_tuple(&self) {
+        println!(
+            "{}: tuples need at least two elements, use the element's type instead",
+            self.e_str
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_range_type(&self, ty: String) {
+        println!(
+            "{}: ranges can only hold integers, found \"{}\"",
+            self.e_str,
+            ty.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_operand_types(&self, op: String, lhs_type: String, rhs_type: String) {
+        println!(
+            "{}: operator \"{}\" can't be used between \"{}\" and \"{}\"",
+            self.e_str,
+            op.magenta().italic(),
+            lhs_type.magenta().italic(),
+            rhs_type.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_condition_type(&self, cond_type: String) {
+        println!(
+            "{}: conditions must be of type \"{}\", found \"{}\"",
+            self.e_str,
+            "bool".magenta().italic(),
+            cond_type.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_lossy_conversion(&self, val_type: String, expected_type: String) {
+        println!(
+            "{}: implicit conversion from \"{}\" to \"{}\" may lose information, use \"{}\" to convert explicitly",
+            self.e_str,
+            val_type.magenta().italic(),
+            expected_type.magenta().italic(),
+            format!("as {}", expected_type).magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_literal_out_of_range(&self, val: i128, ty: String) {
+        println!(
+            "{}: literal \"{}\" doesn't fit in type \"{}\"",
+            self.e_str,
+            val.to_string().magenta().italic(),
+            ty.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_literal_too_large(&self, val: &str) {
+        println!(
+            "{}: literal \"{}\" is too large for any integer type",
+            self.e_str,
+            val.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_invalid_cast(&self, val_type: String, ty: String) {
+        println!(
+            "{}: cannot cast value of type \"{}\" to \"{}\"",
+            self.e_str,
+            val_type.magenta().italic(),
+            ty.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_ty_arg_count(&self, name: &str, expected: usize, found: usize) {
+        println!(
+            "{}: type \"{}\" takes {} type argument{} but {} {} given",
+            self.e_str,
+            name.magenta().italic(),
+            expected,
+            if expected == 1 { "" } else { "s" },
+            found,
+            if found == 1 { "was" } else { "were" }
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_cant_infer_ty_arg(&self, ty_param: &str, name: &str) {
+        println!(
+            "{}: cannot infer type parameter \"{}\" of \"{}\" from the values given",
+            self.e_str,
+            ty_param.magenta().italic(),
+            name.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    // Enum errors
+    pub fn throw_variant_already_used(&self) {
+        println!(
+            "{}: variant name \"{}\" already used",
+            self.e_str,
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_not_an_enum(&self, name: &str) {
+        println!(
+            "{}: type \"{}\" is not an enum",
+            self.e_str,
+            name.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_enum_type_init(&self, name: &str) {
+        println!(
+            "{}: enum \"{}\" can only be built from one of its variants, like \"{}\"",
+            self.e_str,
+            name.magenta().italic(),
+            format!("{}::Variant", name).magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_interface_type_init(&self, name: &str) {
+        println!(
+            "{}: interface \"{}\" has no values of its own, use a type that implements it",
+            self.e_str,
+            name.magenta().italic(),
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_not_an_interface(&self, name: &str) {
+        println!(
+            "{}: type \"{}\" is not an interface",
+            self.e_str,
+            name.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_interface_listed_twice(&self, name: &str) {
+        println!(
+            "{}: interface \"{}\" is already listed",
+            self.e_str,
+            name.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_interface_method_body(&self) {
+        println!(
+            "{}: methods of interfaces only have a signature, end it with \"{}\"",
+            self.e_str,
+            ";".magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_missing_method(&self, ty: &str, method: &str, interface: &str) {
+        println!(
+            "{}: type \"{}\" implements \"{}\" but has no method \"{}\"",
+            self.e_str,
+            ty.magenta().italic(),
+            interface.magenta().italic(),
+            method.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_method_signature(
+        &self,
+        method: &str,
+        interface: &str,
+        expected: String,
+        found: String,
+    ) {
+        println!(
+            "{}: method \"{}\" has signature \"{}\", but \"{}\" declares it as \"{}\"",
+            self.e_str,
+            method.magenta().italic(),
+            found.magenta().italic(),
+            interface.magenta().italic(),
+            expected.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_misplaced_self(&self) {
+        println!(
+            "{}: \"{}\" can only be the first argument of a method",
+            self.e_str,
+            "self".magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_no_method(&self, ty: String) {
+        println!(
+            "{}: values of type \"{}\" don't have a method named \"{}\"",
+            self.e_str,
+            ty.magenta().italic(),
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_op_method(&self, op: String, method: &str, ty: &str, ret_ty: &str) {
+        println!(
+            "{}: \"{}\" between values of type \"{}\" calls their method \"{}\", which has to take \"{}\" and one more argument and return {}",
+            self.e_str,
+            op.magenta().italic(),
+            ty.magenta().italic(),
+            method.magenta().italic(),
+            "self".magenta().italic(),
+            ret_ty
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_static_method_call(&self, ty: &str) {
+        println!(
+            "{}: method \"{}\" has no \"{}\" argument, call it as \"{}\"",
+            self.e_str,
+            self.token.slice.magenta().italic(),
+            "self".magenta().italic(),
+            format!("{}::{}", ty, self.token.slice).magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_no_variant(&self, name: &str) {
+        println!(
+            "{}: enum \"{}\" doesn't have a variant named \"{}\"",
+            self.e_str,
+            name.magenta().italic(),
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_wrong_payload_count(
+        &self,
+        variant_name: &str,
+        expected: usize,
+        found: usize,
+        variant_location: &Location,
+    ) {
+        println!(
+            "{}: variant \"{}\" holds {} value{} but {} {} given",
+            self.e_str,
+            variant_name.magenta().italic(),
+            expected,
+            if expected == 1 { "" } else { "s" },
+            found,
+            if found == 1 { "was" } else { "were" }
+        );
+        self.print(0);
+        self.print_declaration(variant_name, variant_location);
+        exit(0)
+    }
+
+    pub fn throw_wrong_payload_type(
+        &self,
+        variant_name: &str,
+        val_type: String,
+        payload_type: String,
+        variant_location: &Location,
+    ) {
+        println!(
+            "{}: variant \"{}\" holds a value of type \"{}\", found \"{}\"",
+            self.e_str,
+            variant_name.magenta().italic(),
+            payload_type.magenta().italic(),
+            val_type.magenta().italic()
+        );
+        self.print(0);
+        self.print_declaration(variant_name, variant_location);
+        exit(0)
+    }
+
+    pub fn throw_switch_on_non_enum(&self, val_type: String) {
+        println!(
+            "{}: can only switch over enum values, found \"{}\"",
+            self.e_str,
+            val_type.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_variant_already_matched(&self) {
+        println!(
+            "{}: variant \"{}\" is already handled by another case",
+            self.e_str,
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_missing_variants(&self, name: &str, missing: Vec<String>) {
+        println!(
+            "{}: switch over enum \"{}\" doesn't handle variant{} {}, add a case for {} or a \"{}\"",
+            self.e_str,
+            name.magenta().italic(),
+            if missing.len() == 1 { "" } else { "s" },
+            missing
+                .iter()
+                .map(|variant: &String| format!("\"{}\"", variant.magenta().italic()))
+                .collect::<Vec<String>>()
+                .join(", "),
+            if missing.len() == 1 { "it" } else { "them" },
+            "default".magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    // Module errors
+
+    pub fn throw_module_not_found(&self, module: &str) {
+        println!(
+            "{}: cannot find module \"{}\"",
+            self.e_str,
+            module.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
+    pub fn throw_name_not_exported(&self, module: &str) {
+        println!(
+            "{}: module \"{}\" doesn't export \"{}\"",
+            self.e_str,
+            module.magenta().italic(),
+            self.token.slice.magenta().italic()
+        );
+        self.print(0);
+        exit(0)
+    }
+
     // General errors
     pub fn throw_unkown_token(&self) {
         println!(
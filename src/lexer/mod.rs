@@ -34,6 +34,13 @@ impl<'a> Lexer<'a> {
     }
 }
 
+// Tokens of `input` without whitespace and comments
+pub fn lex(input: &str) -> Vec<Token<'_>> {
+    Lexer::new(input)
+        .filter(|token: &Token| !token.token_type.is_trivia())
+        .collect()
+}
+
 impl<'a> Iterator for Lexer<'a> {
     type Item = Token<'a>;
 
@@ -129,10 +129,10 @@ pub enum TokenType {
     Semicolon,
 
     // Types
-    #[token("int")]
+    #[regex(r"int|i8|i16|i32|i64|u8|u16|u32|u64")]
     IntType,
 
-    #[token("float")]
+    #[regex(r"float|f32|f64")]
     FloatType,
 
     #[token("bool")]
@@ -144,10 +144,19 @@ pub enum TokenType {
     #[token("char")]
     CharType,
 
+    #[token("range")]
+    RangeType,
+
     // Keywords
     #[token("type")]
     Type,
 
+    #[token("enum")]
+    Enum,
+
+    #[token("interface")]
+    Interface,
+
     #[token("if")]
     If,
 
@@ -160,6 +169,9 @@ pub enum TokenType {
     #[token("for")]
     For,
 
+    #[token("do")]
+    Do,
+
     #[token("loop")]
     Loop,
 
@@ -196,6 +208,9 @@ pub enum TokenType {
     #[token("in")]
     In,
 
+    #[token("as")]
+    As,
+
     #[token("pub")]
     Pub,
 
@@ -229,9 +244,6 @@ pub enum TokenType {
     #[regex(r"-[0-9]+\.[0-9]+")]
     NegativeFloat,
 
-    #[regex(r"[0-9]+\.\.\.[0-9]+")]
-    Range,
-
     // Strings and Chars
     #[regex(r#""[^"]*""#)]
     String,
@@ -246,6 +258,9 @@ pub enum TokenType {
     #[token("...")]
     Ellipsis,
 
+    #[token("..<")]
+    ExclusiveRange,
+
     #[token(":")]
     Colon,
 
@@ -295,6 +310,21 @@ impl Display for TokenType {
     }
 }
 
+impl TokenType {
+    // Whitespace and comments, they're dropped before parsing
+    pub fn is_trivia(&self) -> bool {
+        matches!(
+            self,
+            TokenType::Space
+                | TokenType::LineFeed
+                | TokenType::CarriageReturn
+                | TokenType::Tab
+                | TokenType::Comment
+                | TokenType::BlockComment
+        )
+    }
+}
+
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct Span {
     pub start: usize,
@@ -1,72 +1,161 @@
 // use compiler::Compiler;
-use lexer::Lexer;
-use parser::Parser;
+use checker::Checker;
+use lexer::lex;
+use parser::{ast::functions::ScopeNode, Parser};
+use project::Project;
 use std::{
-    env::args,
+    collections::HashMap,
+    env::{args, current_dir},
     fs::{read_to_string, File},
     io::Write,
-    path::Path,
+    path::{Path, PathBuf},
     time::{Duration, Instant},
 };
 
-use crate::lexer::token::{Token, TokenType};
+use crate::lexer::token::Token;
 
+mod checker;
 mod error_handler;
 mod lexer;
 mod parser;
+mod project;
 // mod compiler;
 
 fn help() {
     println!("-h, --help   : show this help message");
     println!("-v, --version: show version");
-    println!("    --ast    : show the jast tree file")
+    println!("    --ast    : show the jast tree file");
+    println!("new <name>   : create a new project");
+    println!("build        : compile every module of the current project");
+    println!("run          : build the current project and run its entry point")
 }
 
 fn version() {
     println!("Jay v0.0.0 (2022-016-03)");
 }
 
-fn run(input: &str, file_name: &str) {
-    let lexer: Lexer = Lexer::new(input);
+fn write_ast(ast: String) {
+    match args().nth(2) {
+        Some(ref arg) if arg == "--ast" => {
+            File::create("./ast.jast")
+                .unwrap()
+                .write_all(ast.as_bytes())
+                .unwrap();
+        }
+        _ => {}
+    }
+}
 
-    let mut tokens: Vec<Token> = Vec::new();
+fn run(input: &str, file_name: &str) {
+    let tokens: Vec<Token> = lex(input);
 
-    for token in lexer {
-        if token.token_type != TokenType::Space
-            && token.token_type != TokenType::LineFeed
-            && token.token_type != TokenType::CarriageReturn
-            && token.token_type != TokenType::Comment
-        {
-            tokens.push(token);
-        }
+    if tokens.is_empty() {
+        return;
     }
 
+    let init_tok: Token = tokens[0];
     let lines: Vec<String> = input.lines().map(|line| line.to_string()).collect();
 
-    let mut parser: Parser = Parser::new(tokens, file_name.into(), lines);
+    let mut parser: Parser = Parser::new(tokens, file_name.into(), lines.clone());
     parser.parse();
 
+    Checker::new(init_tok, file_name.into(), lines, parser.impls.clone()).check(&parser.ast);
+
     let ast: String = parser
         .ast
         .into_iter()
         .map(|x| -> String { x.to_string() })
         .collect();
 
-    match args().nth(2) {
-        Some(ref arg) if arg == "--ast" => {
-            File::create("./ast.jast")
-                .unwrap()
-                .write_all(ast.as_bytes())
-                .unwrap();
-        }
-        _ => {}
-    }
+    write_ast(ast);
 
     // let compiler: Compiler = Compiler::new(parser.ast);
 
     // compiler.compile();
 }
 
+fn build() -> Option<Project> {
+    let root: PathBuf = current_dir().expect("Error: failed to read current directory");
+
+    let project: Project = match Project::load(&root) {
+        Ok(project) => project,
+        Err(e) => {
+            println!("Error: {}", e);
+            return None;
+        }
+    };
+
+    println!(
+        "Building {} v{}",
+        project.manifest.package.name, project.manifest.package.version
+    );
+
+    let mut exports: HashMap<String, ScopeNode> = HashMap::new();
+    let mut ast: String = String::new();
+
+    for module in &project.modules {
+        let tokens: Vec<Token> = lex(&module.source);
+        let file_name: String = module
+            .path
+            .strip_prefix(&project.root)
+            .unwrap_or(&module.path)
+            .display()
+            .to_string();
+
+        if tokens.is_empty() {
+            exports.insert(module.name.clone(), ScopeNode::new());
+            continue;
+        }
+
+        let init_tok: Token = tokens[0];
+        let lines: Vec<String> = module.source.lines().map(|line| line.to_string()).collect();
+
+        let mut parser: Parser = Parser::new(tokens, file_name.clone(), lines.clone());
+
+        for (import, resolved) in &module.imports {
+            parser.add_module(import.clone(), exports[resolved].clone());
+        }
+
+        parser.parse();
+
+        Checker::new(init_tok, file_name, lines, parser.impls.clone()).check(&parser.ast);
+
+        ast.extend(parser.ast.iter().map(|x| -> String { x.to_string() }));
+        exports.insert(module.name.clone(), parser.exports);
+    }
+
+    write_ast(ast);
+
+    println!("Built {} module(s)", project.modules.len());
+
+    Some(project)
+}
+
+fn run_project() {
+    if let Some(project) = build() {
+        // There's no backend yet, so there's nothing to execute after the front-end
+        println!(
+            "Error: can't run module \"{}\", code generation isn't available yet",
+            project.entry
+        );
+    }
+}
+
+fn new_project() {
+    let name: String = match args().nth(2) {
+        Some(name) => name,
+        None => {
+            println!("Error: No project name specified");
+            return;
+        }
+    };
+
+    match Project::create(&name) {
+        Ok(root) => println!("Created project \"{}\" in {}", name, root.display()),
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
 fn interpreter() {
     println!("Jay version 0.0.0 (c) 2022");
     loop {
@@ -115,6 +204,11 @@ fn main() {
             Some(ref arg) if arg == "-v" || arg == "--version" => version(),
             Some(ref arg) if arg == "-h" || arg == "--help" => help(),
             Some(ref arg) if arg == "-i" => interpreter(),
+            Some(ref arg) if arg == "new" => new_project(),
+            Some(ref arg) if arg == "build" => {
+                build();
+            }
+            Some(ref arg) if arg == "run" => run_project(),
             _ => compiler(),
         }
     }
@@ -0,0 +1,175 @@
+use super::{functions::ScopeNode, Nodes};
+
+// `else if` is stored as an `else` scope holding a single `IfNode`
+#[derive(Debug, PartialEq, Clone)]
+pub struct IfNode<'a> {
+    pub cond: Box<Nodes<'a>>,
+    pub scope: ScopeNode<'a>,
+    pub else_scope: Option<ScopeNode<'a>>,
+}
+
+impl<'a> IfNode<'a> {
+    pub fn new(
+        cond: Box<Nodes<'a>>,
+        scope: ScopeNode<'a>,
+        else_scope: Option<ScopeNode<'a>>,
+    ) -> Self {
+        Self {
+            cond,
+            scope,
+            else_scope,
+        }
+    }
+}
+
+// Loops can be labeled, `outer: while cond { ... }`, so `break outer;` and `continue outer;`
+// can reach them from nested loops
+#[derive(Debug, PartialEq, Clone)]
+pub struct WhileNode<'a> {
+    pub label: Option<String>,
+    pub cond: Box<Nodes<'a>>,
+    pub scope: ScopeNode<'a>,
+}
+
+impl<'a> WhileNode<'a> {
+    pub fn new(label: Option<String>, cond: Box<Nodes<'a>>, scope: ScopeNode<'a>) -> Self {
+        Self { label, cond, scope }
+    }
+}
+
+// `do { ... } while cond;` runs the body before checking the condition, `continue` jumps to
+// the check
+#[derive(Debug, PartialEq, Clone)]
+pub struct DoWhileNode<'a> {
+    pub label: Option<String>,
+    pub scope: ScopeNode<'a>,
+    pub cond: Box<Nodes<'a>>,
+}
+
+impl<'a> DoWhileNode<'a> {
+    pub fn new(label: Option<String>, scope: ScopeNode<'a>, cond: Box<Nodes<'a>>) -> Self {
+        Self { label, scope, cond }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LoopNode<'a> {
+    pub label: Option<String>,
+    pub scope: ScopeNode<'a>,
+}
+
+impl<'a> LoopNode<'a> {
+    pub fn new(label: Option<String>, scope: ScopeNode<'a>) -> Self {
+        Self { label, scope }
+    }
+}
+
+// `for x in iterable | step { ... }` over a range, an array or the keys of a map.
+// `for i, x in arr { ... }` also gives the index of each element and `for k, v in map { ... }`
+// the value of each key. Ranges go down when the start is the higher end
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForNode<'a> {
+    pub label: Option<String>,
+    pub item: String,
+    pub index: Option<String>,
+    pub iterable: Box<Nodes<'a>>,
+    pub step: Option<Box<Nodes<'a>>>,
+    pub scope: ScopeNode<'a>,
+}
+
+impl<'a> ForNode<'a> {
+    pub fn new(
+        label: Option<String>,
+        item: String,
+        index: Option<String>,
+        iterable: Box<Nodes<'a>>,
+        step: Option<Box<Nodes<'a>>>,
+        scope: ScopeNode<'a>,
+    ) -> Self {
+        Self {
+            label,
+            item,
+            index,
+            iterable,
+            step,
+            scope,
+        }
+    }
+}
+
+// `break;`, `continue;` and `break_if cond;` act on the innermost loop, or on the one with
+// `label`: `break outer;`, `break_if outer: cond;`
+#[derive(Debug, PartialEq, Clone)]
+pub struct BreakNode {
+    pub label: Option<String>,
+}
+
+impl BreakNode {
+    pub fn new(label: Option<String>) -> Self {
+        Self { label }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ContinueNode {
+    pub label: Option<String>,
+}
+
+impl ContinueNode {
+    pub fn new(label: Option<String>) -> Self {
+        Self { label }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BreakIfNode<'a> {
+    pub label: Option<String>,
+    pub cond: Box<Nodes<'a>>,
+}
+
+impl<'a> BreakIfNode<'a> {
+    pub fn new(label: Option<String>, cond: Box<Nodes<'a>>) -> Self {
+        Self { label, cond }
+    }
+}
+
+// `switch val { case Variant(a, b) { ... } default { ... } }` over an enum value, every
+// variant is handled by a case or by `default`
+#[derive(Debug, PartialEq, Clone)]
+pub struct SwitchNode<'a> {
+    pub val: Box<Nodes<'a>>,
+    pub cases: Vec<CaseNode<'a>>,
+    pub default: Option<ScopeNode<'a>>,
+}
+
+impl<'a> SwitchNode<'a> {
+    pub fn new(
+        val: Box<Nodes<'a>>,
+        cases: Vec<CaseNode<'a>>,
+        default: Option<ScopeNode<'a>>,
+    ) -> Self {
+        Self {
+            val,
+            cases,
+            default,
+        }
+    }
+}
+
+// `bindings` name the payload of the variant in order, `_` skips a value
+#[derive(Debug, PartialEq, Clone)]
+pub struct CaseNode<'a> {
+    pub variant: String,
+    pub bindings: Vec<String>,
+    pub scope: ScopeNode<'a>,
+}
+
+impl<'a> CaseNode<'a> {
+    pub fn new(variant: String, bindings: Vec<String>, scope: ScopeNode<'a>) -> Self {
+        Self {
+            variant,
+            bindings,
+            scope,
+        }
+    }
+}
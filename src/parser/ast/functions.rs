@@ -1,36 +1,79 @@
 use either::Either;
 
+use crate::{error_handler::Location, lexer::token::Token};
+
+use crate::parser::math::ast::OpType;
+
 use super::{
     types::TypeNode,
     variables::{ArrayVarType, ValueNode, VarNode, VarType},
     Nodes,
 };
 
+// `location` points at the function's name, calls can be in other files. `ty_params` are
+// the type parameters of generic functions, `T` in `func first<T>(a: T[]): T`. The last
+// argument of `variadic` functions, `nums: int...`, is an unsized array of the values
+// passed after the other arguments
 #[derive(Debug, PartialEq, Clone)]
-pub struct DefineFunctionNode {
+pub struct DefineFunctionNode<'a> {
     pub name: String,
-    args: Vec<ArgNode>,
+    pub ty_params: Vec<String>,
+    pub args: Vec<ArgNode<'a>>,
     pub ret_ty: Option<Either<VarType, ArrayVarType>>,
+    pub variadic: bool,
+    pub location: Location<'a>,
 }
 
-impl DefineFunctionNode {
+impl<'a> DefineFunctionNode<'a> {
     pub fn new(
         name: String,
-        args: Vec<ArgNode>,
+        ty_params: Vec<String>,
+        args: Vec<ArgNode<'a>>,
         ret_ty: Option<Either<VarType, ArrayVarType>>,
+        variadic: bool,
+        location: Location<'a>,
     ) -> Self {
-        Self { name, args, ret_ty }
+        Self {
+            name,
+            ty_params,
+            args,
+            ret_ty,
+            variadic,
+            location,
+        }
+    }
+
+    pub fn arg_index(&self, name: &str) -> Option<usize> {
+        self.args
+            .iter()
+            .position(|arg: &ArgNode<'a>| arg.name == name)
+    }
+
+    // Arguments that have to be given one by one
+    pub fn fixed_args(&self) -> usize {
+        self.args.len() - self.variadic as usize
+    }
+
+    // Argument the value at `i` of a call is passed to, with the type it has to be
+    pub fn arg_at(&self, i: usize) -> Option<(&ArgNode<'a>, Either<VarType, ArrayVarType>)> {
+        if i < self.fixed_args() {
+            return Some((&self.args[i], self.args[i].ty.clone()));
+        }
+
+        let arg: &ArgNode<'a> = self.args.last().filter(|_| self.variadic)?;
+
+        Some((arg, arg.ty.clone().right()?.elem_ty()))
     }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct FunctionNode<'a> {
-    pub define_node: DefineFunctionNode,
+    pub define_node: DefineFunctionNode<'a>,
     pub scope: ScopeNode<'a>,
 }
 
 impl<'a> FunctionNode<'a> {
-    pub fn new(define_node: DefineFunctionNode, scope: ScopeNode<'a>) -> Self {
+    pub fn new(define_node: DefineFunctionNode<'a>, scope: ScopeNode<'a>) -> Self {
         Self { define_node, scope }
     }
 }
@@ -54,32 +97,26 @@ impl<'a> ScopeNode<'a> {
     }
 
     pub fn search_node(
-        &mut self,
+        &self,
         string_to_search: String,
         vec_to_search: u8,
     ) -> (Result<usize, usize>, bool) {
         let node: Result<usize, usize> = match vec_to_search {
             0 => self
                 .var_vec
-                .clone()
-                .into_iter()
-                .map(|x| -> String { x.0 })
-                .collect::<Vec<String>>()
-                .binary_search(&string_to_search),
+                .iter()
+                .position(|x| x.0 == string_to_search)
+                .ok_or(self.var_vec.len()),
             1 => self
                 .func_vec
-                .clone()
-                .into_iter()
-                .map(|x| -> String { x.define_node.name })
-                .collect::<Vec<String>>()
-                .binary_search(&string_to_search),
+                .iter()
+                .position(|x| x.define_node.name == string_to_search)
+                .ok_or(self.func_vec.len()),
             2 => self
                 .type_vec
-                .clone()
-                .into_iter()
-                .map(|x| -> String { x.name })
-                .collect::<Vec<String>>()
-                .binary_search(&string_to_search),
+                .iter()
+                .position(|x| x.name == string_to_search)
+                .ok_or(self.type_vec.len()),
             _ => todo!(),
         };
 
@@ -87,42 +124,143 @@ impl<'a> ScopeNode<'a> {
     }
 }
 
+// `default` is the value used when a call doesn't give one, `b: int = 2`
 #[derive(Debug, PartialEq, Clone)]
-pub struct ArgNode {
+pub struct ArgNode<'a> {
     pub name: String,
     pub ty: Either<VarType, ArrayVarType>,
+    pub default: Option<Nodes<'a>>,
 }
 
-impl ArgNode {
-    pub fn new(name: String, ty: Either<VarType, ArrayVarType>) -> Self {
-        Self { name, ty }
+impl<'a> ArgNode<'a> {
+    pub fn new(
+        name: String,
+        ty: Either<VarType, ArrayVarType>,
+        default: Option<Nodes<'a>>,
+    ) -> Self {
+        Self { name, ty, default }
     }
 }
 
+// `name: val` in a call, passes `val` to the argument called `name`
+#[derive(Debug, PartialEq, Clone)]
+pub struct NamedArgNode<'a> {
+    pub name: String,
+    pub val: Box<Nodes<'a>>,
+    pub token: Token<'a>,
+}
+
+impl<'a> NamedArgNode<'a> {
+    pub fn new(name: String, val: Box<Nodes<'a>>, token: Token<'a>) -> Self {
+        Self { name, val, token }
+    }
+}
+
+// Keeps a copy of the signature, so the call can be checked once the scope is gone
 #[derive(Debug, PartialEq, Clone)]
 pub struct CallFuncNode<'a> {
-    func_node: usize,
-    args: Vec<Nodes<'a>>,
+    pub define_node: DefineFunctionNode<'a>,
+    pub args: Vec<Nodes<'a>>,
+    pub token: Token<'a>,
 }
 
 impl<'a> CallFuncNode<'a> {
-    pub fn new(func_node: usize, args: Vec<Nodes<'a>>) -> Self {
-        Self { func_node, args }
+    pub fn new(
+        define_node: DefineFunctionNode<'a>,
+        args: Vec<Nodes<'a>>,
+        token: Token<'a>,
+    ) -> Self {
+        Self {
+            define_node,
+            args,
+            token,
+        }
     }
 }
 
+// `a + b` between values of a user type calls the method the type defines for the operator.
+// `!=` and the other comparisons come from `eq` and `lt` by swapping the operands or negating
+// the result, `a >= b` is `!a.lt(b)`
+#[derive(Debug, PartialEq, Clone)]
+pub struct OpMethodNode<'a> {
+    pub op: OpType,
+    pub call: CallFuncNode<'a>,
+    pub negate: bool,
+}
+
+impl<'a> OpMethodNode<'a> {
+    pub fn new(op: OpType, call: CallFuncNode<'a>, negate: bool) -> Self {
+        Self { op, call, negate }
+    }
+}
+
+// `ret_val` is `None` for a bare `return;` in a function without a return type
 #[derive(Debug, PartialEq, Clone)]
 pub struct ReturnNode<'a> {
-    ret_val: ValueNode<'a>,
+    pub ret_val: Option<ValueNode<'a>>,
 }
 
 impl<'a> ReturnNode<'a> {
-    pub fn new(ret_val: ValueNode<'a>) -> Self {
+    pub fn new(ret_val: Option<ValueNode<'a>>) -> Self {
         Self { ret_val }
     }
 }
 
+// `return_if cond: value;`, or `return_if cond;` in a function without a return type
 #[derive(Debug, PartialEq, Clone)]
 pub struct ReturnIfNode<'a> {
-    ret_val: Box<Nodes<'a>>,
+    pub cond: Box<Nodes<'a>>,
+    pub ret_val: Option<ValueNode<'a>>,
+}
+
+impl<'a> ReturnIfNode<'a> {
+    pub fn new(cond: Box<Nodes<'a>>, ret_val: Option<ValueNode<'a>>) -> Self {
+        Self { cond, ret_val }
+    }
+}
+
+// `func(a: int): int { ... }` used as a value. `captures` are the variables of enclosing
+// scopes the body uses, the lambda keeps them alive after those scopes end
+#[derive(Debug, PartialEq, Clone)]
+pub struct LambdaNode<'a> {
+    pub func: FunctionNode<'a>,
+    pub captures: Vec<VarNode<'a>>,
+    pub token: Token<'a>,
+}
+
+impl<'a> LambdaNode<'a> {
+    pub fn new(func: FunctionNode<'a>, captures: Vec<VarNode<'a>>, token: Token<'a>) -> Self {
+        Self {
+            func,
+            captures,
+            token,
+        }
+    }
+
+    pub fn get_ty(&self) -> VarType {
+        let define_node: &DefineFunctionNode<'a> = &self.func.define_node;
+
+        VarType::Func {
+            args: define_node
+                .args
+                .iter()
+                .map(|arg: &ArgNode<'a>| arg.ty.clone())
+                .collect(),
+            ret_ty: define_node.ret_ty.clone().map(Box::new),
+        }
+    }
+}
+
+// Call through a value of a function type, like a variable holding a lambda or the result
+// of another call, `make_adder(1)(2)`
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallFuncValueNode<'a> {
+    pub callee: Box<Nodes<'a>>,
+    pub args: Vec<Nodes<'a>>,
+}
+
+impl<'a> CallFuncValueNode<'a> {
+    pub fn new(callee: Box<Nodes<'a>>, args: Vec<Nodes<'a>>) -> Self {
+        Self { callee, args }
+    }
 }
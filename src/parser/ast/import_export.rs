@@ -0,0 +1,22 @@
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportNode {
+    pub names: Vec<String>,
+    pub module: String,
+}
+
+impl ImportNode {
+    pub fn new(names: Vec<String>, module: String) -> Self {
+        Self { names, module }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExportNode {
+    pub names: Vec<String>,
+}
+
+impl ExportNode {
+    pub fn new(names: Vec<String>) -> Self {
+        Self { names }
+    }
+}
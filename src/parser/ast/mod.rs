@@ -1,22 +1,35 @@
 use core::fmt;
 use std::fmt::{Display, Formatter};
 
-use crate::lexer::token::Token;
+use either::Either::{self, Left};
+
+use crate::lexer::token::{Token, TokenType};
 
 use self::{
-    functions::{CallFuncNode, FunctionNode, /*ReturnIfNode,*/ ReturnNode},
-    types::{PrimitiveTypeNode, TypeNode},
+    control_flow::{
+        BreakIfNode, BreakNode, ContinueNode, DoWhileNode, ForNode, IfNode, LoopNode, SwitchNode,
+        WhileNode,
+    },
+    functions::{
+        CallFuncNode, CallFuncValueNode, FunctionNode, LambdaNode, NamedArgNode, OpMethodNode,
+        ReturnIfNode, ReturnNode,
+    },
+    import_export::{ExportNode, ImportNode},
+    types::{CastNode, PrimitiveTypeNode, TypeNode},
     variables::{
-        AssignToVarArrNode, AssignToVarNode, CallVarArrNode, CallVarNode, InitTypeNode, VarNode,
+        ArrFunc, ArrRepeatNode, ArrayVarType, AssignToMapNode, AssignToVarArrNode, AssignToVarNode,
+        CallArrFuncNode, CallFieldNode, CallMapFuncNode, CallMapNode, CallStrFuncNode, CallStrNode,
+        CallVarArrNode, CallVarNode, DestructureNode, FloatType, InitEnumNode, InitTypeNode,
+        IntType, InterpolationNode, MapFunc, MapNode, SpreadNode, StrFunc, TupleNode, ValueNode,
+        VarNode, VarType,
     },
 };
 
-use super::math::{
-    ast::{MathIdNode, MathNumberNode, MathOpTypeNode},
-    ProcessedMathNode,
-};
+use super::math::{ast::MathOpTypeNode, ProcessedMathNode};
 
+pub mod control_flow;
 pub mod functions;
+pub mod import_export;
 pub mod types;
 pub mod variables;
 
@@ -25,26 +38,61 @@ pub enum Nodes<'a> {
     // AST
     PrimitiveTypeNode(PrimitiveTypeNode<'a>),
     TypeNode(TypeNode<'a>),
+    CastNode(CastNode<'a>),
 
     // Variables
     VarNode(VarNode<'a>),
+    DestructureNode(DestructureNode<'a>),
     CallVarNode(CallVarNode<'a>),
     CallVarArrNode(CallVarArrNode<'a>),
+    CallArrFuncNode(CallArrFuncNode<'a>),
+    CallFieldNode(CallFieldNode<'a>),
     AssignToVarNode(AssignToVarNode<'a>),
     AssignToVarArrNode(AssignToVarArrNode<'a>),
+    CallMapNode(CallMapNode<'a>),
+    CallMapFuncNode(CallMapFuncNode<'a>),
+    AssignToMapNode(AssignToMapNode<'a>),
+    CallStrNode(CallStrNode<'a>),
+    CallStrFuncNode(CallStrFuncNode<'a>),
+    InterpolationNode(InterpolationNode<'a>),
     InitTypeNode(InitTypeNode<'a>),
+    InitEnumNode(InitEnumNode<'a>),
+    ValueNode(ValueNode<'a>),
+    ArrRepeatNode(ArrRepeatNode<'a>),
+    SpreadNode(SpreadNode<'a>),
+    TupleNode(TupleNode<'a>),
+    MapNode(MapNode<'a>),
 
     // Functions
     FunctionNode(FunctionNode<'a>),
     CallFuncNode(CallFuncNode<'a>),
+    LambdaNode(LambdaNode<'a>),
+    CallFuncValueNode(CallFuncValueNode<'a>),
+    // `obj.method()`, the value is the first argument
+    CallMethodNode(CallFuncNode<'a>),
+    OpMethodNode(OpMethodNode<'a>),
+    NamedArgNode(NamedArgNode<'a>),
     ReturnNode(ReturnNode<'a>),
-    // ReturnIfNode(ReturnIfNode<'a>),
+    ReturnIfNode(ReturnIfNode<'a>),
+
+    // Control flow
+    IfNode(IfNode<'a>),
+    WhileNode(WhileNode<'a>),
+    DoWhileNode(DoWhileNode<'a>),
+    ForNode(ForNode<'a>),
+    LoopNode(LoopNode<'a>),
+    BreakNode(BreakNode),
+    ContinueNode(ContinueNode),
+    BreakIfNode(BreakIfNode<'a>),
+    SwitchNode(SwitchNode<'a>),
+
+    // Modules
+    ImportNode(ImportNode),
+    ExportNode(ExportNode),
 
     // External Math AST
     ProcessedMathNode(ProcessedMathNode<'a>),
     MathOpTypeNode(MathOpTypeNode),
-    MathNumberNode(MathNumberNode<'a>),
-    MathIdNode(MathIdNode<'a>),
 
     // General
     Eol,
@@ -52,9 +100,169 @@ pub enum Nodes<'a> {
 }
 
 impl<'a> Nodes<'a> {
-    pub fn get_primitive(&self) -> Option<Token<'a>> {
+    // Value of integer expressions that can be computed at compile time, only immutable
+    // variables initialized with a constant count as constants
+    pub fn const_eval(&self) -> Option<i128> {
+        match self {
+            Nodes::PrimitiveTypeNode(token)
+                if token.0.token_type == TokenType::Number
+                    || token.0.token_type == TokenType::NegativeNumber =>
+            {
+                token.0.slice.parse().ok()
+            }
+            Nodes::CallVarNode(var) if !var.0 .2 => var.0 .1 .0.as_ref().left()?.const_eval(),
+            Nodes::CallVarArrNode(var) if !var.0 .0 .2 => {
+                let indexes: Vec<isize> = var
+                    .1
+                    .iter()
+                    .map(|index| index.index.const_eval()?.try_into().ok())
+                    .collect::<Option<Vec<isize>>>()?;
+
+                var.0 .0 .1.get_elem(&indexes)?.const_eval()
+            }
+            Nodes::ProcessedMathNode(node) => node.const_eval(),
+            _ => None,
+        }
+    }
+
+    // Number literals and arithmetic between them, their type depends on where they're used,
+    // `get_ty` only gives their default type
+    pub fn is_num_literal(&self) -> bool {
+        match self {
+            Nodes::PrimitiveTypeNode(token) => matches!(
+                token.0.token_type,
+                TokenType::Number
+                    | TokenType::NegativeNumber
+                    | TokenType::Float
+                    | TokenType::NegativeFloat
+            ),
+            Nodes::ProcessedMathNode(node) => {
+                node.out_stream.iter().all(|node: &Nodes<'a>| match node {
+                    Nodes::MathOpTypeNode(op) => op.is_arithmetic(),
+                    node => node.is_num_literal(),
+                })
+            }
+            _ => false,
+        }
+    }
+
+    // Type of the value produced by the node, `None` when it doesn't produce one or when
+    // its operands don't fit together
+    pub fn get_ty(&self) -> Option<Either<VarType, ArrayVarType>> {
+        match self {
+            Nodes::PrimitiveTypeNode(token) => match token.0.token_type {
+                TokenType::Number | TokenType::NegativeNumber => {
+                    Some(Left(VarType::Int { ty: IntType::I64 }))
+                }
+                TokenType::Float | TokenType::NegativeFloat => {
+                    Some(Left(VarType::Float { ty: FloatType::F64 }))
+                }
+                TokenType::String => Some(Left(VarType::String)),
+                TokenType::Bool => Some(Left(VarType::Bool)),
+                TokenType::Char => Some(Left(VarType::Char)),
+                _ => None,
+            },
+            Nodes::CastNode(node) => Some(Left(node.ty.clone())),
+            Nodes::CallVarNode(var) => Some(var.0 .1 .1.clone()),
+            Nodes::CallVarArrNode(var) => var.0 .0 .1 .1.clone().right()?.index_ty(var.1.len()),
+            Nodes::CallArrFuncNode(var) => {
+                let arr_ty: ArrayVarType = var.0 .0 .1 .1.clone().right()?;
+
+                match var.1 {
+                    ArrFunc::Len => Some(Left(VarType::Int { ty: IntType::I64 })),
+                    ArrFunc::Pop => Some(arr_ty.elem_ty()),
+                    ArrFunc::Push(_) => None,
+                }
+            }
+            Nodes::CallMapNode(var) => match &var.0 .0 .1 .1 {
+                Left(VarType::Map { key: _, val }) => Some(*val.clone()),
+                _ => None,
+            },
+            Nodes::CallMapFuncNode(var) => match var.1 {
+                MapFunc::Len => Some(Left(VarType::Int { ty: IntType::I64 })),
+                MapFunc::Remove(_) => None,
+            },
+            Nodes::CallStrNode(var) => match var.1.get_ty()? {
+                Left(VarType::Range { ty: _ }) => Some(Left(VarType::String)),
+                _ => Some(Left(VarType::Char)),
+            },
+            Nodes::CallStrFuncNode(var) => match var.1 {
+                StrFunc::Len => Some(Left(VarType::Int { ty: IntType::I64 })),
+            },
+            Nodes::InterpolationNode(_) => Some(Left(VarType::String)),
+            Nodes::CallFieldNode(field) => Some(field.2.clone()),
+            Nodes::CallFuncNode(call) | Nodes::CallMethodNode(call) => {
+                call.define_node.ret_ty.clone()
+            }
+            Nodes::OpMethodNode(node) if node.negate => Some(Left(VarType::Bool)),
+            Nodes::OpMethodNode(node) => node.call.define_node.ret_ty.clone(),
+            Nodes::LambdaNode(lambda) => Some(Left(lambda.get_ty())),
+            Nodes::CallFuncValueNode(call) => match call.callee.get_ty()? {
+                Left(VarType::Func { args: _, ret_ty }) => ret_ty.map(|ty| *ty),
+                _ => None,
+            },
+            Nodes::ValueNode(value) => Some(value.1.clone()),
+            Nodes::TupleNode(tuple) => Some(Left(tuple.get_ty()?)),
+            Nodes::MapNode(node) => {
+                let (key, val) = node.entries.first()?;
+
+                Some(Left(VarType::Map {
+                    key: Box::new(key.get_ty()?),
+                    val: Box::new(val.get_ty()?),
+                }))
+            }
+            Nodes::InitTypeNode(node) => Some(Left(VarType::Type {
+                name: node.name.clone(),
+                args: node.args.clone(),
+            })),
+            Nodes::InitEnumNode(node) => Some(Left(VarType::Type {
+                name: node.name.clone(),
+                args: vec![],
+            })),
+            Nodes::ProcessedMathNode(node) => node.get_ty(),
+            _ => None,
+        }
+    }
+
+    // Leftmost token of an expression, used to point at it in diagnostics
+    pub fn get_token(&self) -> Option<Token<'a>> {
         match self {
             Nodes::PrimitiveTypeNode(token) => Some(token.0),
+            Nodes::CastNode(node) => node.val.get_token(),
+            Nodes::CallVarNode(var) => Some(var.1),
+            Nodes::CallVarArrNode(var) => Some(var.0 .1),
+            Nodes::CallArrFuncNode(var) => Some(var.0 .1),
+            Nodes::CallFieldNode(field) => field.0.get_token(),
+            Nodes::CallMapNode(var) => Some(var.0 .1),
+            Nodes::CallMapFuncNode(var) => Some(var.0 .1),
+            Nodes::AssignToMapNode(node) => Some(node.0 .0 .1),
+            Nodes::CallStrNode(var) => Some(var.0 .1),
+            Nodes::CallStrFuncNode(var) => Some(var.0 .1),
+            Nodes::InterpolationNode(node) => Some(node.token),
+            Nodes::CallFuncNode(call) => Some(call.token),
+            Nodes::OpMethodNode(node) => Some(node.call.token),
+            Nodes::CallMethodNode(call) => call
+                .args
+                .first()
+                .and_then(|obj: &Nodes<'a>| obj.get_token())
+                .or(Some(call.token)),
+            Nodes::LambdaNode(lambda) => Some(lambda.token),
+            Nodes::CallFuncValueNode(call) => call.callee.get_token(),
+            Nodes::NamedArgNode(arg) => Some(arg.token),
+            Nodes::InitTypeNode(node) => Some(node.token),
+            Nodes::InitEnumNode(node) => Some(node.token),
+            Nodes::AssignToVarNode(node) => Some(node.0 .1),
+            Nodes::AssignToVarArrNode(node) => Some(node.0 .0 .1),
+            Nodes::ArrRepeatNode(node) => node.val.get_token(),
+            Nodes::SpreadNode(node) => Some(node.token),
+            Nodes::TupleNode(node) => Some(node.token),
+            Nodes::MapNode(node) => Some(node.token),
+            Nodes::ValueNode(value) => match &value.0 {
+                Either::Left(node) => node.get_token(),
+                Either::Right(elems) => elems.first()?.0.get_token(),
+            },
+            // Operands keep their order in the output stream, so the first one is the leftmost
+            Nodes::ProcessedMathNode(node) => node.out_stream.first()?.get_token(),
             _ => None,
         }
     }
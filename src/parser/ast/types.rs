@@ -1,15 +1,30 @@
 use either::Either;
 
-use crate::lexer::token::Token;
+use crate::{error_handler::Location, lexer::token::Token};
 
 use super::{
-    functions::FunctionNode,
+    functions::{DefineFunctionNode, FunctionNode},
     variables::{ArrayVarType, VarType},
+    Nodes,
 };
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct PrimitiveTypeNode<'a>(pub Token<'a>);
 
+// `val as ty`, `token` is the type the value is cast to
+#[derive(Debug, PartialEq, Clone)]
+pub struct CastNode<'a> {
+    pub val: Box<Nodes<'a>>,
+    pub ty: VarType,
+    pub token: Token<'a>,
+}
+
+impl<'a> CastNode<'a> {
+    pub fn new(val: Box<Nodes<'a>>, ty: VarType, token: Token<'a>) -> Self {
+        Self { val, ty, token }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct TypeArgNode<'a> {
     pub name: String,
@@ -27,14 +42,104 @@ impl<'a> TypeArgNode<'a> {
     }
 }
 
+// `variants` is only set for enums and `methods` for interfaces, both share the namespace of
+// types. `ty_params` are the type parameters of generic types, `A` and `B` in
+// `type Pair<A, B>`, and `interfaces` the ones a type implements, `type Circle: Shape`
 #[derive(Debug, PartialEq, Clone)]
 pub struct TypeNode<'a> {
     pub name: String,
+    pub ty_params: Vec<String>,
     pub args: Vec<TypeArgNode<'a>>,
+    pub variants: Option<Vec<VariantNode<'a>>>,
+    pub methods: Option<Vec<DefineFunctionNode<'a>>>,
+    pub interfaces: Vec<String>,
+    pub location: Location<'a>,
 }
 
 impl<'a> TypeNode<'a> {
-    pub fn new(name: String, args: Vec<TypeArgNode<'a>>) -> Self {
-        Self { name, args }
+    pub fn new(
+        name: String,
+        ty_params: Vec<String>,
+        args: Vec<TypeArgNode<'a>>,
+        location: Location<'a>,
+    ) -> Self {
+        Self {
+            name,
+            ty_params,
+            args,
+            variants: None,
+            methods: None,
+            interfaces: vec![],
+            location,
+        }
+    }
+
+    pub fn new_enum(name: String, variants: Vec<VariantNode<'a>>, location: Location<'a>) -> Self {
+        Self {
+            name,
+            ty_params: vec![],
+            args: vec![],
+            variants: Some(variants),
+            methods: None,
+            interfaces: vec![],
+            location,
+        }
+    }
+
+    pub fn new_interface(
+        name: String,
+        methods: Vec<DefineFunctionNode<'a>>,
+        location: Location<'a>,
+    ) -> Self {
+        Self {
+            name,
+            ty_params: vec![],
+            args: vec![],
+            variants: None,
+            methods: Some(methods),
+            interfaces: vec![],
+            location,
+        }
+    }
+
+    // Method `name` with its signature, interfaces only have signatures
+    pub fn method(&self, name: &str) -> Option<DefineFunctionNode<'a>> {
+        match &self.methods {
+            Some(methods) => methods
+                .iter()
+                .find(|method: &&DefineFunctionNode<'a>| method.name == name)
+                .cloned(),
+            None => self
+                .args
+                .iter()
+                .find_map(|arg: &TypeArgNode<'a>| match &arg.val {
+                    Either::Right(func) if func.define_node.name == name => {
+                        Some(func.define_node.clone())
+                    }
+                    _ => None,
+                }),
+        }
+    }
+}
+
+// `Variant(ty, ...)` in an enum, unit variants have an empty payload
+#[derive(Debug, PartialEq, Clone)]
+pub struct VariantNode<'a> {
+    pub name: String,
+    pub payload: Vec<Either<VarType, ArrayVarType>>,
+    pub location: Location<'a>,
+}
+
+impl<'a> VariantNode<'a> {
+    pub fn new(
+        name: String,
+        payload: Vec<Either<VarType, ArrayVarType>>,
+        location: Location<'a>,
+    ) -> Self {
+        Self {
+            name,
+            payload,
+            location,
+        }
     }
 }
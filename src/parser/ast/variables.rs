@@ -1,106 +1,874 @@
 use core::fmt;
-use std::{
-    fmt::{Display, Formatter},
-    isize,
-};
+use std::fmt::{Display, Formatter};
 
 use either::Either;
 
-use super::Nodes;
+use crate::lexer::token::Token;
+
+use super::{types::VariantNode, Nodes};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum IntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
+impl Display for IntType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            if self.is_signed() { "i" } else { "u" },
+            self.bits()
+        )
+    }
+}
+
+impl IntType {
+    // `int` is an alias of `i64`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "i8" => Some(IntType::I8),
+            "i16" => Some(IntType::I16),
+            "i32" => Some(IntType::I32),
+            "i64" | "int" => Some(IntType::I64),
+            "u8" => Some(IntType::U8),
+            "u16" => Some(IntType::U16),
+            "u32" => Some(IntType::U32),
+            "u64" => Some(IntType::U64),
+            _ => None,
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        match self {
+            IntType::I8 | IntType::U8 => 8,
+            IntType::I16 | IntType::U16 => 16,
+            IntType::I32 | IntType::U32 => 32,
+            IntType::I64 | IntType::U64 => 64,
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        matches!(
+            self,
+            IntType::I8 | IntType::I16 | IntType::I32 | IntType::I64
+        )
+    }
+
+    pub fn contains(&self, val: i128) -> bool {
+        if self.is_signed() {
+            let max: i128 = (1 << (self.bits() - 1)) - 1;
+
+            (-max - 1..=max).contains(&val)
+        } else {
+            (0..1 << self.bits()).contains(&val)
+        }
+    }
+
+    // Whether every value of `self` is also a value of `to`
+    pub fn widens_to(&self, to: &IntType) -> bool {
+        match (self.is_signed(), to.is_signed()) {
+            (true, false) => false,
+            (false, true) => self.bits() < to.bits(),
+            _ => self.bits() <= to.bits(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FloatType {
+    F32,
+    F64,
+}
+
+impl Display for FloatType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "f{}", self.bits())
+    }
+}
+
+impl FloatType {
+    // `float` is an alias of `f64`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "f32" => Some(FloatType::F32),
+            "f64" | "float" => Some(FloatType::F64),
+            _ => None,
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        match self {
+            FloatType::F32 => 32,
+            FloatType::F64 => 64,
+        }
+    }
+
+    // Integers up to this many bits are represented exactly
+    pub fn mantissa_bits(&self) -> u32 {
+        match self {
+            FloatType::F32 => 24,
+            FloatType::F64 => 53,
+        }
+    }
+}
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum VarType {
-    Int,
-    Float,
+    Int {
+        ty: IntType,
+    },
+    Float {
+        ty: FloatType,
+    },
     String,
     Char,
     Bool,
-    Type { name: String },
+    // `args` are the type arguments of generic types, `Pair<int, string>`
+    Type {
+        name: String,
+        args: Vec<Either<VarType, ArrayVarType>>,
+    },
+    // A type parameter of the generic function or type it's used in
+    Generic {
+        name: String,
+    },
+    // Functions used as values, `func(int, int): int`
+    Func {
+        args: Vec<Either<VarType, ArrayVarType>>,
+        ret_ty: Option<Box<Either<VarType, ArrayVarType>>>,
+    },
+    // `0...10`, `ty` is the type of the values in the range
+    Range {
+        ty: IntType,
+    },
+    // `(int, string)`, always has at least two elements
+    Tuple {
+        elems: Vec<Either<VarType, ArrayVarType>>,
+    },
+    // `map<string, int>`, keys are integers, strings, chars or bools
+    Map {
+        key: Box<Either<VarType, ArrayVarType>>,
+        val: Box<Either<VarType, ArrayVarType>>,
+    },
+}
+
+fn fmt_ty(ty: &Either<VarType, ArrayVarType>) -> String {
+    either::for_both!(ty, ty => ty.to_string())
 }
 
 impl Display for VarType {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            VarType::Int { ty } => write!(f, "{}", ty),
+            VarType::Float { ty } => write!(f, "{}", ty),
+            VarType::String => write!(f, "string"),
+            VarType::Char => write!(f, "char"),
+            VarType::Bool => write!(f, "bool"),
+            VarType::Generic { name } => write!(f, "{}", name),
+            VarType::Range { ty } => write!(f, "range<{}>", ty),
+            VarType::Map { key, val } => write!(f, "map<{}, {}>", fmt_ty(key), fmt_ty(val)),
+            VarType::Tuple { elems } => write!(
+                f,
+                "({})",
+                elems.iter().map(fmt_ty).collect::<Vec<String>>().join(", ")
+            ),
+            VarType::Type { name, args } if !args.is_empty() => write!(
+                f,
+                "{}<{}>",
+                name,
+                args.iter().map(fmt_ty).collect::<Vec<String>>().join(", ")
+            ),
+            VarType::Type { name, args: _ } => write!(f, "{}", name),
+            VarType::Func { args, ret_ty } => {
+                write!(
+                    f,
+                    "func({})",
+                    args.iter().map(fmt_ty).collect::<Vec<String>>().join(", ")
+                )?;
+
+                match ret_ty {
+                    Some(ret_ty) => write!(f, ": {}", fmt_ty(ret_ty)),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl VarType {
+    pub fn is_num(&self) -> bool {
+        matches!(self, VarType::Int { ty: _ } | VarType::Float { ty: _ })
+    }
+
+    // Whether a value of `self` can be used where `to` is expected without losing information,
+    // any other conversion between numbers needs an `as`
+    pub fn widens_to(&self, to: &VarType) -> bool {
+        match (self, to) {
+            (VarType::Int { ty }, VarType::Int { ty: to }) => ty.widens_to(to),
+            (VarType::Int { ty }, VarType::Float { ty: to }) => ty.bits() <= to.mantissa_bits(),
+            (VarType::Float { ty }, VarType::Float { ty: to }) => ty.bits() <= to.bits(),
+            _ => self == to,
+        }
+    }
+
+    // Number literals have no type of their own, integer literals become whatever number
+    // they're used as and float literals whatever float, `self` is the literal's default type
+    pub fn literal_fits(&self, to: &VarType) -> bool {
+        match self {
+            VarType::Int { ty: _ } => to.is_num(),
+            VarType::Float { ty: _ } => matches!(to, VarType::Float { ty: _ }),
+            _ => false,
+        }
+    }
+
+    // Anything scalar turns into its text, strings parse into numbers and chars convert to and
+    // from their code point
+    pub fn can_cast_to(&self, to: &VarType) -> bool {
+        match (self, to) {
+            _ if self == to || (self.is_num() && to.is_num()) => true,
+            (
+                VarType::Int { ty: _ } | VarType::Float { ty: _ } | VarType::Char | VarType::Bool,
+                VarType::String,
+            ) => true,
+            (VarType::String, to) => to.is_num(),
+            (VarType::Char, VarType::Int { ty: _ }) | (VarType::Int { ty: _ }, VarType::Char) => {
+                true
+            }
+            _ => false,
+        }
     }
 }
 
+// `init_num` is `None` for unsized arrays (`int[]`), which can be used as parameters and
+// return types and, unless declared with `const`, grow with `push`/`pop`.
+// `Array` is an array of arrays, so `int[3][4]` is `Array { ty: Int { 4 }, init_num: 3 }`.
 #[derive(Debug, PartialEq, Clone)]
 pub enum ArrayVarType {
-    Int { init_num: isize },
-    Float { init_num: isize },
-    String { init_num: isize },
-    Char { init_num: isize },
-    Bool { init_num: isize },
-    Type { name: String, init_num: isize },
+    Int {
+        ty: IntType,
+        init_num: Option<isize>,
+    },
+    Float {
+        ty: FloatType,
+        init_num: Option<isize>,
+    },
+    String {
+        init_num: Option<isize>,
+    },
+    Char {
+        init_num: Option<isize>,
+    },
+    Bool {
+        init_num: Option<isize>,
+    },
+    Type {
+        name: String,
+        args: Vec<Either<VarType, ArrayVarType>>,
+        init_num: Option<isize>,
+    },
+    Generic {
+        name: String,
+        init_num: Option<isize>,
+    },
+    Func {
+        args: Vec<Either<VarType, ArrayVarType>>,
+        ret_ty: Option<Box<Either<VarType, ArrayVarType>>>,
+        init_num: Option<isize>,
+    },
+    Range {
+        ty: IntType,
+        init_num: Option<isize>,
+    },
+    Tuple {
+        elems: Vec<Either<VarType, ArrayVarType>>,
+        init_num: Option<isize>,
+    },
+    Map {
+        key: Box<Either<VarType, ArrayVarType>>,
+        val: Box<Either<VarType, ArrayVarType>>,
+        init_num: Option<isize>,
+    },
+    Array {
+        ty: Box<ArrayVarType>,
+        init_num: Option<isize>,
+    },
 }
 
+// `int[3][4]`, the outermost array has the first size
 impl Display for ArrayVarType {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        write!(f, "{}", self.to_var_type())?;
+
+        let mut arr_ty: &ArrayVarType = self;
+
+        loop {
+            match arr_ty.get_init_num() {
+                Some(init_num) => write!(f, "[{}]", init_num)?,
+                None => write!(f, "[]")?,
+            }
+
+            match arr_ty {
+                ArrayVarType::Array { ty, init_num: _ } => arr_ty = ty,
+                _ => return Ok(()),
+            }
+        }
     }
 }
 
 impl ArrayVarType {
     pub fn to_var_type(&self) -> VarType {
         match self {
-            ArrayVarType::Int { init_num: _ } => VarType::Int,
-            ArrayVarType::Float { init_num: _ } => VarType::Float,
+            ArrayVarType::Int { ty, init_num: _ } => VarType::Int { ty: *ty },
+            ArrayVarType::Float { ty, init_num: _ } => VarType::Float { ty: *ty },
             ArrayVarType::String { init_num: _ } => VarType::String,
             ArrayVarType::Bool { init_num: _ } => VarType::Bool,
             ArrayVarType::Char { init_num: _ } => VarType::Char,
             ArrayVarType::Type {
-                name: _,
+                name,
+                args,
+                init_num: _,
+            } => VarType::Type {
+                name: name.clone(),
+                args: args.clone(),
+            },
+            ArrayVarType::Generic { name, init_num: _ } => VarType::Generic { name: name.clone() },
+            ArrayVarType::Func {
+                args,
+                ret_ty,
                 init_num: _,
-            } => todo!(),
+            } => VarType::Func {
+                args: args.clone(),
+                ret_ty: ret_ty.clone(),
+            },
+            ArrayVarType::Range { ty, init_num: _ } => VarType::Range { ty: *ty },
+            ArrayVarType::Tuple { elems, init_num: _ } => VarType::Tuple {
+                elems: elems.clone(),
+            },
+            ArrayVarType::Map {
+                key,
+                val,
+                init_num: _,
+            } => VarType::Map {
+                key: key.clone(),
+                val: val.clone(),
+            },
+            ArrayVarType::Array { ty, init_num: _ } => ty.to_var_type(),
         }
     }
 
-    pub fn get_init_num(&self) -> &isize {
+    pub fn get_init_num(&self) -> &Option<isize> {
         match self {
-            ArrayVarType::Int { init_num } => init_num,
-            ArrayVarType::Float { init_num } => init_num,
+            ArrayVarType::Int { ty: _, init_num } => init_num,
+            ArrayVarType::Float { ty: _, init_num } => init_num,
             ArrayVarType::String { init_num } => init_num,
             ArrayVarType::Bool { init_num } => init_num,
             ArrayVarType::Char { init_num } => init_num,
             ArrayVarType::Type {
                 name: _,
-                init_num: _,
-            } => todo!(),
+                args: _,
+                init_num,
+            } => init_num,
+            ArrayVarType::Generic { name: _, init_num } => init_num,
+            ArrayVarType::Func {
+                args: _,
+                ret_ty: _,
+                init_num,
+            } => init_num,
+            ArrayVarType::Range { ty: _, init_num } => init_num,
+            ArrayVarType::Tuple { elems: _, init_num } => init_num,
+            ArrayVarType::Map {
+                key: _,
+                val: _,
+                init_num,
+            } => init_num,
+            ArrayVarType::Array { ty: _, init_num } => init_num,
+        }
+    }
+
+    pub fn elem_ty(&self) -> Either<VarType, ArrayVarType> {
+        match self {
+            ArrayVarType::Array { ty, init_num: _ } => Either::Right(*ty.clone()),
+            _ => Either::Left(self.to_var_type()),
+        }
+    }
+
+    // Type of the value after applying `count` indexes, `int[3][4]` indexed once is `int[4]`
+    pub fn index_ty(&self, count: usize) -> Option<Either<VarType, ArrayVarType>> {
+        match (count, self.elem_ty()) {
+            (0, _) => Some(Either::Right(self.clone())),
+            (1, elem_ty) => Some(elem_ty),
+            (_, Either::Right(elem_ty)) => elem_ty.index_ty(count - 1),
+            (_, Either::Left(_)) => None,
+        }
+    }
+
+    // Array with elements of `elem_ty`, the inverse of `elem_ty()`
+    pub fn from_elem_ty(elem_ty: Either<VarType, ArrayVarType>, init_num: Option<isize>) -> Self {
+        match elem_ty {
+            Either::Left(VarType::Int { ty }) => ArrayVarType::Int { ty, init_num },
+            Either::Left(VarType::Float { ty }) => ArrayVarType::Float { ty, init_num },
+            Either::Left(VarType::String) => ArrayVarType::String { init_num },
+            Either::Left(VarType::Bool) => ArrayVarType::Bool { init_num },
+            Either::Left(VarType::Char) => ArrayVarType::Char { init_num },
+            Either::Left(VarType::Type { name, args }) => ArrayVarType::Type {
+                name,
+                args,
+                init_num,
+            },
+            Either::Left(VarType::Generic { name }) => ArrayVarType::Generic { name, init_num },
+            Either::Left(VarType::Func { args, ret_ty }) => ArrayVarType::Func {
+                args,
+                ret_ty,
+                init_num,
+            },
+            Either::Left(VarType::Range { ty }) => ArrayVarType::Range { ty, init_num },
+            Either::Left(VarType::Tuple { elems }) => ArrayVarType::Tuple { elems, init_num },
+            Either::Left(VarType::Map { key, val }) => ArrayVarType::Map { key, val, init_num },
+            Either::Right(ty) => ArrayVarType::Array {
+                ty: Box::new(ty),
+                init_num,
+            },
+        }
+    }
+
+    pub fn is_unsized(&self) -> bool {
+        self.get_init_num().is_none()
+    }
+
+    // A fixed `int[5]` can be used where an `int[]` is expected, but not the other way around
+    pub fn fits(&self, expected: &ArrayVarType) -> bool {
+        let elems_fit: bool = match (self.elem_ty(), expected.elem_ty()) {
+            (Either::Left(elem_ty), Either::Left(expected_elem_ty)) => elem_ty == expected_elem_ty,
+            (Either::Right(elem_ty), Either::Right(expected_elem_ty)) => {
+                elem_ty.fits(&expected_elem_ty)
+            }
+            _ => false,
+        };
+
+        elems_fit && (expected.is_unsized() || self.get_init_num() == expected.get_init_num())
+    }
+}
+
+// Replaces the type parameters `params` in `ty` with the matching `args`
+pub fn substitute_ty(
+    ty: &Either<VarType, ArrayVarType>,
+    params: &[String],
+    args: &[Either<VarType, ArrayVarType>],
+) -> Either<VarType, ArrayVarType> {
+    match ty {
+        Either::Left(VarType::Generic { name }) => params
+            .iter()
+            .position(|param: &String| param == name)
+            .map_or(ty.clone(), |i: usize| args[i].clone()),
+        Either::Left(VarType::Type {
+            name,
+            args: ty_args,
+        }) => Either::Left(VarType::Type {
+            name: name.clone(),
+            args: ty_args
+                .iter()
+                .map(|ty_arg| substitute_ty(ty_arg, params, args))
+                .collect(),
+        }),
+        Either::Left(VarType::Func {
+            args: func_args,
+            ret_ty,
+        }) => Either::Left(VarType::Func {
+            args: func_args
+                .iter()
+                .map(|arg| substitute_ty(arg, params, args))
+                .collect(),
+            ret_ty: ret_ty
+                .as_ref()
+                .map(|ret_ty| Box::new(substitute_ty(ret_ty, params, args))),
+        }),
+        Either::Left(VarType::Tuple { elems }) => Either::Left(VarType::Tuple {
+            elems: elems
+                .iter()
+                .map(|elem| substitute_ty(elem, params, args))
+                .collect(),
+        }),
+        Either::Left(VarType::Map { key, val }) => Either::Left(VarType::Map {
+            key: Box::new(substitute_ty(key, params, args)),
+            val: Box::new(substitute_ty(val, params, args)),
+        }),
+        Either::Left(_) => ty.clone(),
+        Either::Right(arr_ty) => Either::Right(ArrayVarType::from_elem_ty(
+            substitute_ty(&arr_ty.elem_ty(), params, args),
+            *arr_ty.get_init_num(),
+        )),
+    }
+}
+
+// Binds the type parameters `params` found in `param_ty` to the matching part of `arg_ty`,
+// parameters that already have a type keep it
+pub fn infer_ty_args(
+    param_ty: &Either<VarType, ArrayVarType>,
+    arg_ty: &Either<VarType, ArrayVarType>,
+    params: &[String],
+    args: &mut [Option<Either<VarType, ArrayVarType>>],
+) {
+    match (param_ty, arg_ty) {
+        (Either::Left(VarType::Generic { name }), _) => {
+            if let Some(i) = params.iter().position(|param: &String| param == name) {
+                if args[i].is_none() {
+                    args[i] = Some(arg_ty.clone());
+                }
+            }
+        }
+        (
+            Either::Left(VarType::Type {
+                name,
+                args: param_args,
+            }),
+            Either::Left(VarType::Type {
+                name: arg_name,
+                args: arg_args,
+            }),
+        ) if name == arg_name => {
+            for (param_ty, arg_ty) in param_args.iter().zip(arg_args) {
+                infer_ty_args(param_ty, arg_ty, params, args);
+            }
+        }
+        (
+            Either::Left(VarType::Func {
+                args: param_args,
+                ret_ty: param_ret_ty,
+            }),
+            Either::Left(VarType::Func {
+                args: arg_args,
+                ret_ty: arg_ret_ty,
+            }),
+        ) => {
+            for (param_ty, arg_ty) in param_args.iter().zip(arg_args) {
+                infer_ty_args(param_ty, arg_ty, params, args);
+            }
+
+            if let (Some(param_ret_ty), Some(arg_ret_ty)) = (param_ret_ty, arg_ret_ty) {
+                infer_ty_args(param_ret_ty, arg_ret_ty, params, args);
+            }
+        }
+        (
+            Either::Left(VarType::Tuple { elems: param_elems }),
+            Either::Left(VarType::Tuple { elems: arg_elems }),
+        ) => {
+            for (param_ty, arg_ty) in param_elems.iter().zip(arg_elems) {
+                infer_ty_args(param_ty, arg_ty, params, args);
+            }
+        }
+        (
+            Either::Left(VarType::Map {
+                key: param_key,
+                val: param_val,
+            }),
+            Either::Left(VarType::Map {
+                key: arg_key,
+                val: arg_val,
+            }),
+        ) => {
+            infer_ty_args(param_key, arg_key, params, args);
+            infer_ty_args(param_val, arg_val, params, args);
+        }
+        (Either::Right(param_ty), Either::Right(arg_ty)) => {
+            infer_ty_args(&param_ty.elem_ty(), &arg_ty.elem_ty(), params, args)
         }
+        _ => {}
     }
 }
 
+// An empty array literal (`[]`) fills fixed arrays with the element's default value
 #[derive(Debug, PartialEq, Clone)]
 pub struct ValueNode<'a>(
     pub Either<Box<Nodes<'a>>, Vec<ArrElem<'a>>>,
     pub Either<VarType, ArrayVarType>,
 );
 
+impl<'a> ValueNode<'a> {
+    // Walks a literal array value, only works when every level was written as a literal
+    pub fn get_elem(&self, indexes: &[isize]) -> Option<Box<Nodes<'a>>> {
+        let elem: &Nodes<'a> = match &self.0 {
+            Either::Left(node) => match node.as_ref() {
+                Nodes::ArrRepeatNode(node) if (0..node.count).contains(indexes.first()?) => {
+                    &node.val
+                }
+                _ => return None,
+            },
+            // Spreads move the elements after them
+            Either::Right(elems) if elems.iter().any(ArrElem::is_spread) => return None,
+            Either::Right(elems) => &elems.get(*indexes.first()? as usize)?.0,
+        };
+
+        match (indexes.len(), elem) {
+            (1, _) => Some(Box::new(elem.clone())),
+            (_, Nodes::ValueNode(value)) => value.get_elem(&indexes[1..]),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct VarNode<'a>(pub String, pub ValueNode<'a>, pub bool);
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ArrElem<'a>(pub Box<Nodes<'a>>, pub isize);
 
+impl<'a> ArrElem<'a> {
+    pub fn is_spread(&self) -> bool {
+        matches!(self.0.as_ref(), Nodes::SpreadNode(_))
+    }
+
+    // Number of values in a literal with these elements, `None` when it has unsized spreads
+    pub fn literal_len(elems: &[ArrElem<'a>]) -> Option<isize> {
+        elems
+            .iter()
+            .map(|elem: &ArrElem<'a>| match elem.0.as_ref() {
+                Nodes::SpreadNode(spread) => spread.len(),
+                _ => Some(1),
+            })
+            .sum()
+    }
+}
+
+// `[val; count]`
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrRepeatNode<'a> {
+    pub val: Box<Nodes<'a>>,
+    pub count: isize,
+}
+
+impl<'a> ArrRepeatNode<'a> {
+    pub fn new(val: Box<Nodes<'a>>, count: isize) -> Self {
+        Self { val, count }
+    }
+}
+
+// `...arr` inside an array literal or for a variadic argument, `token` is the `...`
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpreadNode<'a> {
+    pub val: Box<Nodes<'a>>,
+    pub token: Token<'a>,
+}
+
+impl<'a> SpreadNode<'a> {
+    pub fn new(val: Box<Nodes<'a>>, token: Token<'a>) -> Self {
+        Self { val, token }
+    }
+
+    // Number of values the spread adds, `None` when it spreads an unsized array
+    pub fn len(&self) -> Option<isize> {
+        match self.val.get_ty()? {
+            Either::Right(arr_ty) => *arr_ty.get_init_num(),
+            Either::Left(_) => None,
+        }
+    }
+}
+
+// `(a, b)`, `token` is the `(`
+#[derive(Debug, PartialEq, Clone)]
+pub struct TupleNode<'a> {
+    pub elems: Vec<Nodes<'a>>,
+    pub token: Token<'a>,
+}
+
+impl<'a> TupleNode<'a> {
+    pub fn new(elems: Vec<Nodes<'a>>, token: Token<'a>) -> Self {
+        Self { elems, token }
+    }
+
+    pub fn get_ty(&self) -> Option<VarType> {
+        Some(VarType::Tuple {
+            elems: self
+                .elems
+                .iter()
+                .map(|elem: &Nodes<'a>| elem.get_ty())
+                .collect::<Option<Vec<Either<VarType, ArrayVarType>>>>()?,
+        })
+    }
+}
+
+// `{"a": 1, "b": 2}`, `token` is the `{`. Map literals are only parsed as values of a known
+// map type, like array literals
+#[derive(Debug, PartialEq, Clone)]
+pub struct MapNode<'a> {
+    pub entries: Vec<(Nodes<'a>, Nodes<'a>)>,
+    pub token: Token<'a>,
+}
+
+impl<'a> MapNode<'a> {
+    pub fn new(entries: Vec<(Nodes<'a>, Nodes<'a>)>, token: Token<'a>) -> Self {
+        Self { entries, token }
+    }
+}
+
+// `let (a, b) = val;` declares a variable for each element of the tuple `val`, `_` skips one
+#[derive(Debug, PartialEq, Clone)]
+pub struct DestructureNode<'a> {
+    pub names: Vec<String>,
+    pub val: ValueNode<'a>,
+    pub is_mut: bool,
+}
+
+impl<'a> DestructureNode<'a> {
+    pub fn new(names: Vec<String>, val: ValueNode<'a>, is_mut: bool) -> Self {
+        Self { names, val, is_mut }
+    }
+
+    // Written like the declaration, `(a, b)`, for diagnostics
+    pub fn name(&self) -> String {
+        format!("({})", self.names.join(", "))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallVarNode<'a>(pub VarNode<'a>, pub Token<'a>);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallVarArrNode<'a>(pub CallVarNode<'a>, pub Vec<ArrIndex<'a>>);
+
+// `bounds_check` is set when the index can't be validated at compile time, because either
+// the index isn't constant or the array is unsized, so it has to be checked when running
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrIndex<'a> {
+    pub index: Box<Nodes<'a>>,
+    pub bounds_check: bool,
+}
+
+impl<'a> ArrIndex<'a> {
+    pub fn new(index: Box<Nodes<'a>>, bounds_check: bool) -> Self {
+        Self {
+            index,
+            bounds_check,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ArrFunc<'a> {
+    Len,
+    Push(Box<Nodes<'a>>),
+    Pop,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallArrFuncNode<'a>(pub CallVarNode<'a>, pub ArrFunc<'a>);
+
+// `map[key]`, the key has to be in the map
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallMapNode<'a>(pub CallVarNode<'a>, pub Box<Nodes<'a>>);
+
+// `map[key] = val;` inserts the key or replaces its value
+#[derive(Debug, PartialEq, Clone)]
+pub struct AssignToMapNode<'a>(pub CallMapNode<'a>, pub Box<Nodes<'a>>);
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum MapFunc<'a> {
+    Len,
+    Remove(Box<Nodes<'a>>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallMapFuncNode<'a>(pub CallVarNode<'a>, pub MapFunc<'a>);
+
+// `str[i]` is the char at `i` and `str[a..<b]` the string between `a` and `b`
 #[derive(Debug, PartialEq, Clone)]
-pub struct CallVarNode<'a>(pub VarNode<'a>);
+pub struct CallStrNode<'a>(pub CallVarNode<'a>, pub Box<Nodes<'a>>);
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct CallVarArrNode<'a>(pub CallVarNode<'a>, pub isize);
+pub enum StrFunc {
+    Len,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallStrFuncNode<'a>(pub CallVarNode<'a>, pub StrFunc);
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum StrPart<'a> {
+    Text(String),
+    Expr(Box<Nodes<'a>>),
+}
+
+// `"hello {name}"`, `token` is the whole string. `{{` and `}}` are the braces themselves
+#[derive(Debug, PartialEq, Clone)]
+pub struct InterpolationNode<'a> {
+    pub parts: Vec<StrPart<'a>>,
+    pub token: Token<'a>,
+}
+
+impl<'a> InterpolationNode<'a> {
+    pub fn new(parts: Vec<StrPart<'a>>, token: Token<'a>) -> Self {
+        Self { parts, token }
+    }
+}
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct AssignToVarNode<'a>(pub CallVarNode<'a>, pub Box<Nodes<'a>>);
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct AssignToVarArrNode<'a>(pub CallVarArrNode<'a>, pub isize, pub Box<Nodes<'a>>);
+pub struct AssignToVarArrNode<'a>(pub CallVarArrNode<'a>, pub Box<Nodes<'a>>);
 
+// `obj.field`, the type of the field is looked up while parsing
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallFieldNode<'a>(
+    pub Box<Nodes<'a>>,
+    pub String,
+    pub Either<VarType, ArrayVarType>,
+);
+
+// `Enum::Variant(args)`, keeps a copy of the variant so the payload can be checked
+#[derive(Debug, PartialEq, Clone)]
+pub struct InitEnumNode<'a> {
+    pub name: String,
+    pub variant: VariantNode<'a>,
+    pub args: Vec<Nodes<'a>>,
+    pub token: Token<'a>,
+}
+
+impl<'a> InitEnumNode<'a> {
+    pub fn new(
+        name: String,
+        variant: VariantNode<'a>,
+        args: Vec<Nodes<'a>>,
+        token: Token<'a>,
+    ) -> Self {
+        Self {
+            name,
+            variant,
+            args,
+            token,
+        }
+    }
+}
+
+// `args` are the type arguments inferred from the fields of generic types, the fields are
+// parsed for their type with those arguments
 #[derive(Debug, PartialEq, Clone)]
 pub struct InitTypeNode<'a> {
-    fields: Vec<ValueNode<'a>>,
+    pub name: String,
+    pub args: Vec<Either<VarType, ArrayVarType>>,
+    pub fields: Vec<(String, ValueNode<'a>)>,
+    pub token: Token<'a>,
 }
 
 impl<'a> InitTypeNode<'a> {
-    pub fn new(fields: Vec<ValueNode<'a>>) -> Self {
-        Self { fields }
+    pub fn new(
+        name: String,
+        args: Vec<Either<VarType, ArrayVarType>>,
+        fields: Vec<(String, ValueNode<'a>)>,
+        token: Token<'a>,
+    ) -> Self {
+        Self {
+            name,
+            args,
+            fields,
+            token,
+        }
     }
 }
@@ -1,4 +1,9 @@
-use crate::lexer::token::Token;
+use core::fmt;
+use std::fmt::{Display, Formatter};
+
+use either::Either;
+
+use crate::parser::ast::variables::VarType;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum OpType {
@@ -6,40 +11,185 @@ pub enum OpType {
     Minus,
     Multiply,
     Divide,
-}
+    Modulo,
+    Power,
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct MathNumberNode<'a>(pub Token<'a>);
+    Equal,
+    NotEqual,
+    LessThan,
+    GreaterThan,
+    LessThanOrEqual,
+    GreaterThanOrEqual,
+    // `key in map`
+    In,
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct MathIdNode<'a> {
-    id_name: String,
-    id_value: Token<'a>,
+    And,
+    Or,
+
+    // `a...b` includes `b`, `a..<b` doesn't
+    InclusiveRange,
+    ExclusiveRange,
+
+    // Only used while converting the expression, they never reach the output stream
+    OpenParen,
+    CloseParen,
 }
 
-impl<'a> MathIdNode<'a> {
-    pub fn new(tok: Token<'a>) -> Self {
-        Self {
-            id_name: tok.slice.into(),
-            id_value: tok,
-        }
+impl Display for OpType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                OpType::Plus => "+",
+                OpType::Minus => "-",
+                OpType::Multiply => "*",
+                OpType::Divide => "/",
+                OpType::Modulo => "%",
+                OpType::Power => "^",
+                OpType::Equal => "==",
+                OpType::NotEqual => "!=",
+                OpType::LessThan => "<",
+                OpType::GreaterThan => ">",
+                OpType::LessThanOrEqual => "<=",
+                OpType::GreaterThanOrEqual => ">=",
+                OpType::In => "in",
+                OpType::And => "&&",
+                OpType::Or => "||",
+                OpType::InclusiveRange => "...",
+                OpType::ExclusiveRange => "..<",
+                OpType::OpenParen => "(",
+                OpType::CloseParen => ")",
+            }
+        )
     }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct MathOpTypeNode {
-    op: OpType,
-    prio: i8,
+    pub op: OpType,
+    pub prio: i8,
 }
 
 impl MathOpTypeNode {
     pub fn new(op: OpType) -> Self {
-        let prio: i8 = if op == OpType::Plus || op == OpType::Minus {
-            1
-        } else {
-            2
+        let prio: i8 = match op {
+            OpType::InclusiveRange | OpType::ExclusiveRange => 1,
+            OpType::Or => 2,
+            OpType::And => 3,
+            OpType::Equal
+            | OpType::NotEqual
+            | OpType::LessThan
+            | OpType::GreaterThan
+            | OpType::LessThanOrEqual
+            | OpType::GreaterThanOrEqual
+            | OpType::In => 4,
+            OpType::Plus | OpType::Minus => 5,
+            OpType::Multiply | OpType::Divide | OpType::Modulo => 6,
+            OpType::Power => 7,
+            OpType::OpenParen | OpType::CloseParen => 0,
         };
 
         Self { op, prio }
     }
+
+    pub fn is_right_assoc(&self) -> bool {
+        self.op == OpType::Power
+    }
+
+    pub fn is_arithmetic(&self) -> bool {
+        matches!(
+            self.op,
+            OpType::Plus
+                | OpType::Minus
+                | OpType::Multiply
+                | OpType::Divide
+                | OpType::Modulo
+                | OpType::Power
+        )
+    }
+
+    // `lhs op rhs` for integers known at compile time
+    pub fn eval(&self, lhs: i128, rhs: i128) -> Option<i128> {
+        match self.op {
+            OpType::Plus => lhs.checked_add(rhs),
+            OpType::Minus => lhs.checked_sub(rhs),
+            OpType::Multiply => lhs.checked_mul(rhs),
+            OpType::Divide => lhs.checked_div(rhs),
+            OpType::Modulo => lhs.checked_rem(rhs),
+            OpType::Power => lhs.checked_pow(rhs.try_into().ok()?),
+            _ => None,
+        }
+    }
+
+    // Type of `lhs op rhs`, `None` when the operator can't be applied to the operands.
+    // Numbers of different types are converted to the one the other widens to
+    pub fn result_ty(&self, lhs: &VarType, rhs: &VarType) -> Option<VarType> {
+        let num_ty: Option<VarType> = match (lhs, rhs) {
+            (lhs, rhs) if !lhs.is_num() || !rhs.is_num() => None,
+            (lhs, rhs) if lhs.widens_to(rhs) => Some(rhs.clone()),
+            (lhs, rhs) if rhs.widens_to(lhs) => Some(lhs.clone()),
+            _ => None,
+        };
+
+        match (&self.op, lhs, rhs) {
+            (OpType::OpenParen | OpType::CloseParen, _, _) => None,
+            (OpType::Equal | OpType::NotEqual, lhs, rhs) if lhs == rhs => Some(VarType::Bool),
+            (OpType::And | OpType::Or, VarType::Bool, VarType::Bool) => Some(VarType::Bool),
+            (OpType::And | OpType::Or, _, _) => None,
+            (OpType::In, lhs, VarType::Map { key, val: _ }) => {
+                matches!(key.as_ref(), Either::Left(key) if lhs.widens_to(key))
+                    .then_some(VarType::Bool)
+            }
+            (OpType::In, _, _) => None,
+            (OpType::Plus, VarType::String, VarType::String) => Some(VarType::String),
+            // Strings compare by their characters and chars by their code points
+            (
+                OpType::LessThan
+                | OpType::GreaterThan
+                | OpType::LessThanOrEqual
+                | OpType::GreaterThanOrEqual,
+                VarType::String,
+                VarType::String,
+            )
+            | (
+                OpType::LessThan
+                | OpType::GreaterThan
+                | OpType::LessThanOrEqual
+                | OpType::GreaterThanOrEqual,
+                VarType::Char,
+                VarType::Char,
+            ) => Some(VarType::Bool),
+            (OpType::InclusiveRange | OpType::ExclusiveRange, _, _) => match num_ty {
+                Some(VarType::Int { ty }) => Some(VarType::Range { ty }),
+                _ => None,
+            },
+            _ if self.is_arithmetic() => num_ty,
+            _ => num_ty.map(|_| VarType::Bool),
+        }
+    }
+
+    // Same as `result_ty`, but a number literal operand first takes the type of the other one
+    pub fn literal_result_ty(
+        &self,
+        (lhs, lhs_literal): (&VarType, bool),
+        (rhs, rhs_literal): (&VarType, bool),
+    ) -> Option<VarType> {
+        // A literal key takes the key type of the map
+        if let (OpType::In, VarType::Map { key, val: _ }) = (&self.op, rhs) {
+            if let (true, Either::Left(key)) = (lhs_literal, key.as_ref()) {
+                if lhs.literal_fits(key) {
+                    return self.result_ty(key, rhs);
+                }
+            }
+        }
+
+        if lhs_literal && lhs.literal_fits(rhs) {
+            self.result_ty(rhs, rhs)
+        } else if rhs_literal && rhs.literal_fits(lhs) {
+            self.result_ty(lhs, lhs)
+        } else {
+            self.result_ty(lhs, rhs)
+        }
+    }
 }
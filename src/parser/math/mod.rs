@@ -1,49 +1,163 @@
 pub mod ast;
 
-use crate::{
-    error_handler::Error,
-    lexer::token::{Token, TokenType},
-};
+use either::Either::{self, Left};
 
-use self::ast::{
-    MathIdNode, MathNumberNode, MathOpTypeNode,
-    OpType::{Divide, Minus, Multiply, Plus},
-};
+use crate::lexer::token::TokenType;
 
-use super::ast::Nodes;
+use self::ast::{MathOpTypeNode, OpType};
 
+use super::ast::{
+    variables::{ArrayVarType, VarType},
+    Nodes,
+};
+
+// `out_stream` holds the expression in reverse polish notation
 #[derive(Debug, PartialEq, Clone)]
 pub struct ProcessedMathNode<'a> {
-    out_stream: Vec<Nodes<'a>>,
+    pub out_stream: Vec<Nodes<'a>>,
 }
 
 impl<'a> ProcessedMathNode<'a> {
     pub fn new(out_stream: Vec<Nodes<'a>>) -> Self {
         Self { out_stream }
     }
+
+    pub fn const_eval(&self) -> Option<i128> {
+        let mut stack: Vec<i128> = vec![];
+
+        for node in &self.out_stream {
+            match node {
+                Nodes::MathOpTypeNode(op) => {
+                    let rhs: i128 = stack.pop()?;
+                    let lhs: i128 = stack.pop()?;
+
+                    stack.push(op.eval(lhs, rhs)?);
+                }
+                _ => stack.push(node.const_eval()?),
+            }
+        }
+
+        stack.pop()
+    }
+
+    // Both ends of the expression if it builds a range
+    pub fn range_ends(&self) -> Option<(Nodes<'a>, Nodes<'a>)> {
+        let (Nodes::MathOpTypeNode(op), rest) = self.out_stream.split_last()? else {
+            return None;
+        };
+
+        if !matches!(op.op, OpType::InclusiveRange | OpType::ExclusiveRange) {
+            return None;
+        }
+
+        // The rhs is the shortest tail of `rest` holding one operand more than operators
+        let mut needed: usize = 1;
+        let mut start: usize = rest.len();
+
+        while needed > 0 {
+            start -= 1;
+
+            match rest[start] {
+                Nodes::MathOpTypeNode(_) => needed += 1,
+                _ => needed -= 1,
+            }
+        }
+
+        let to_node = |nodes: &[Nodes<'a>]| match nodes {
+            [node] => node.clone(),
+            nodes => Nodes::ProcessedMathNode(ProcessedMathNode::new(nodes.to_vec())),
+        };
+
+        Some((to_node(&rest[..start]), to_node(&rest[start..])))
+    }
+
+    // Each entry of the stack also keeps whether it's a number literal
+    pub fn get_ty(&self) -> Option<Either<VarType, ArrayVarType>> {
+        let mut stack: Vec<(Option<Either<VarType, ArrayVarType>>, bool)> = vec![];
+
+        for node in &self.out_stream {
+            match node {
+                Nodes::MathOpTypeNode(op) => {
+                    let (rhs, rhs_literal) = stack.pop()?;
+                    let (lhs, lhs_literal) = stack.pop()?;
+
+                    stack.push(match (lhs, rhs) {
+                        (Some(Left(lhs)), Some(Left(rhs))) => (
+                            op.literal_result_ty((&lhs, lhs_literal), (&rhs, rhs_literal))
+                                .map(Left),
+                            lhs_literal && rhs_literal && op.is_arithmetic(),
+                        ),
+                        _ => (None, false),
+                    });
+                }
+                _ => stack.push((node.get_ty(), node.is_num_literal())),
+            }
+        }
+
+        stack.pop()?.0
+    }
 }
 
-pub fn process_math_node<'a>(
-    tok_stream: Vec<Token<'a>>,
-    line_string: String,
-    file_name: String,
-) -> ProcessedMathNode<'a> {
-    let mut out_stream: Vec<Nodes<'a>> = vec![];
-
-    for tok in tok_stream {
-        out_stream.push(match tok.token_type {
-            TokenType::Number => Nodes::MathNumberNode(MathNumberNode(tok)),
-            TokenType::Plus => Nodes::MathOpTypeNode(MathOpTypeNode::new(Plus)),
-            TokenType::Minus => Nodes::MathOpTypeNode(MathOpTypeNode::new(Minus)),
-            TokenType::Multiply => Nodes::MathOpTypeNode(MathOpTypeNode::new(Multiply)),
-            TokenType::Divide => Nodes::MathOpTypeNode(MathOpTypeNode::new(Divide)),
-            TokenType::Identifier => Nodes::MathIdNode(MathIdNode::new(tok)),
-            _ => {
-                Error::new(tok, line_string.clone(), file_name.clone())
-                    .throw_unkown_token_in_math_expr();
-                Nodes::NullNode
+pub fn get_op_type(token_type: TokenType) -> Option<OpType> {
+    match token_type {
+        TokenType::Plus => Some(OpType::Plus),
+        TokenType::Minus => Some(OpType::Minus),
+        TokenType::Multiply => Some(OpType::Multiply),
+        TokenType::Divide => Some(OpType::Divide),
+        TokenType::Modulo => Some(OpType::Modulo),
+        TokenType::Power => Some(OpType::Power),
+        TokenType::Equal => Some(OpType::Equal),
+        TokenType::NotEqual => Some(OpType::NotEqual),
+        TokenType::LessThan => Some(OpType::LessThan),
+        TokenType::GreaterThan => Some(OpType::GreaterThan),
+        TokenType::LessThanOrEqual => Some(OpType::LessThanOrEqual),
+        TokenType::GreaterThanOrEqual => Some(OpType::GreaterThanOrEqual),
+        TokenType::In => Some(OpType::In),
+        TokenType::And => Some(OpType::And),
+        TokenType::Or => Some(OpType::Or),
+        TokenType::Ellipsis => Some(OpType::InclusiveRange),
+        TokenType::ExclusiveRange => Some(OpType::ExclusiveRange),
+        _ => None,
+    }
+}
+
+// Shunting-yard: `in_stream` is the expression as written, operands and operators alternated
+pub fn process_math_node(in_stream: Vec<Nodes<'_>>) -> ProcessedMathNode<'_> {
+    let mut out_stream: Vec<Nodes> = vec![];
+    let mut op_stack: Vec<MathOpTypeNode> = vec![];
+
+    for node in in_stream {
+        match node {
+            Nodes::MathOpTypeNode(op) if op.op == OpType::OpenParen => op_stack.push(op),
+            Nodes::MathOpTypeNode(op) if op.op == OpType::CloseParen => {
+                while let Some(top) = op_stack.pop() {
+                    if top.op == OpType::OpenParen {
+                        break;
+                    }
+
+                    out_stream.push(Nodes::MathOpTypeNode(top));
+                }
+            }
+            Nodes::MathOpTypeNode(op) => {
+                while let Some(top) = op_stack.last() {
+                    if top.op == OpType::OpenParen
+                        || top.prio < op.prio
+                        || (top.prio == op.prio && op.is_right_assoc())
+                    {
+                        break;
+                    }
+
+                    out_stream.push(Nodes::MathOpTypeNode(op_stack.pop().unwrap()));
+                }
+
+                op_stack.push(op);
             }
-        })
+            _ => out_stream.push(node),
+        }
+    }
+
+    while let Some(top) = op_stack.pop() {
+        out_stream.push(Nodes::MathOpTypeNode(top));
     }
 
     ProcessedMathNode::new(out_stream)
@@ -1,15 +1,29 @@
 use either::Either::{self, Left, Right};
-use std::vec;
+use logos::Logos;
+use std::{collections::HashMap, vec};
 
 use crate::lexer::token::{Token, TokenType};
-use crate::{error_handler::Error, lexer::token::Span};
+use crate::{
+    error_handler::{Error, Location},
+    lexer::token::Span,
+};
 
+use self::ast::control_flow::{
+    BreakIfNode, BreakNode, CaseNode, ContinueNode, DoWhileNode, ForNode, IfNode, LoopNode,
+    SwitchNode, WhileNode,
+};
 use self::ast::functions::{
-    ArgNode, CallFuncNode, DefineFunctionNode, FunctionNode, /*ReturnIfNode,*/ ReturnNode,
-    ScopeNode,
+    ArgNode, CallFuncNode, CallFuncValueNode, DefineFunctionNode, FunctionNode, LambdaNode,
+    NamedArgNode, OpMethodNode, ReturnIfNode, ReturnNode, ScopeNode,
+};
+use self::ast::import_export::{ExportNode, ImportNode};
+use self::ast::types::{CastNode, TypeArgNode, TypeNode, VariantNode};
+use self::ast::variables::{
+    infer_ty_args, substitute_ty, ArrFunc, ArrIndex, ArrRepeatNode, AssignToMapNode,
+    AssignToVarArrNode, CallArrFuncNode, CallFieldNode, CallMapFuncNode, CallMapNode,
+    CallStrFuncNode, CallStrNode, DestructureNode, FloatType, InitEnumNode, InitTypeNode, IntType,
+    InterpolationNode, MapFunc, MapNode, SpreadNode, StrFunc, StrPart, TupleNode, ValueNode,
 };
-use self::ast::types::{TypeArgNode, TypeNode};
-use self::ast::variables::{AssignToVarArrNode, InitTypeNode, ValueNode};
 use self::ast::{
     types::PrimitiveTypeNode,
     variables::{
@@ -17,10 +31,22 @@ use self::ast::{
     },
     Nodes,
 };
+use self::math::{
+    ast::{MathOpTypeNode, OpType},
+    ProcessedMathNode,
+};
 
 pub(crate) mod ast;
 mod math;
 
+pub(crate) fn ty_to_string(ty: &Option<Either<VarType, ArrayVarType>>) -> String {
+    match ty {
+        Some(Left(ty)) => ty.to_string(),
+        Some(Right(ty)) => ty.to_string(),
+        None => "None".to_owned(),
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Parser<'a> {
     token_stream: Vec<Token<'a>>,
@@ -33,6 +59,23 @@ pub struct Parser<'a> {
     error_handler: Error<'a>,
 
     current_scope: ScopeNode<'a>,
+    current_func: Option<DefineFunctionNode<'a>>,
+    // Labels of the loops around the current statement, innermost last
+    loop_labels: Vec<Option<String>>,
+    ty_params: Vec<String>,
+    split_gt: bool,
+    // Set before parsing the iterable of a `for`, a `|` outside parentheses starts its step
+    step_follows: bool,
+    // One frame per lambda being parsed, with the number of variables visible where it starts
+    // and the ones from before it that its body uses
+    captures: Vec<(usize, Vec<VarNode<'a>>)>,
+    // Type of `self` while parsing the methods of a type or interface
+    self_ty: Option<VarType>,
+
+    modules: HashMap<String, ScopeNode<'a>>,
+    pub exports: ScopeNode<'a>,
+    // Interfaces implemented by each type, values of the type fit them
+    pub impls: HashMap<String, Vec<String>>,
 }
 
 impl<'a> Parser<'a> {
@@ -52,10 +95,27 @@ impl<'a> Parser<'a> {
             error_handler: Error::new(init_tok, "".to_owned(), file_name),
 
             current_scope: ScopeNode::new(),
+            current_func: None,
+            loop_labels: vec![],
+            ty_params: vec![],
+            split_gt: false,
+            step_follows: false,
+            captures: vec![],
+            self_ty: None,
+
+            modules: HashMap::new(),
+            exports: ScopeNode::new(),
+            impls: HashMap::new(),
         }
     }
 
+    pub fn add_module(&mut self, name: String, exports: ScopeNode<'a>) {
+        self.modules.insert(name, exports);
+    }
+
     pub fn parse(&mut self) {
+        self.collect_declarations();
+
         while self.tok_i < self.token_stream.len() {
             self.next(1);
 
@@ -89,9 +149,164 @@ impl<'a> Parser<'a> {
             self.error_handler.throw_name_not_defined(vec_to_search);
         }
 
+        if let (0, Ok(idx)) = (vec_to_search, result.0) {
+            self.capture(idx);
+        }
+
         result
     }
 
+    // Records the variable at `idx` in the lambdas it was declared outside of
+    fn capture(&mut self, idx: usize) {
+        let var: &VarNode<'a> = &self.current_scope.var_vec[idx];
+
+        for (outer_vars, captures) in self.captures.iter_mut() {
+            if idx < *outer_vars && !captures.iter().any(|capture| capture.0 == var.0) {
+                captures.push(var.clone());
+            }
+        }
+    }
+
+    // * Declarations
+
+    // Registers imports and every top-level type and function signature before parsing the
+    // rest, so items can be used before the place they're declared in. Parsing the actual
+    // declarations later replaces the registered entries instead of reporting them as
+    // already used, because their locations match.
+    fn collect_declarations(&mut self) {
+        let mut items: Vec<usize> = vec![];
+        let mut depth: usize = 0;
+
+        for (i, token) in self.token_stream.iter().enumerate() {
+            match token.token_type {
+                TokenType::OpenBrace => depth += 1,
+                TokenType::CloseBrace => depth = depth.saturating_sub(1),
+                TokenType::Import if depth == 0 => items.push(i),
+                // `func(` starts a lambda or a function type instead. Declarations without a
+                // name are reported when they're parsed
+                TokenType::Type | TokenType::Enum | TokenType::Interface | TokenType::Func
+                    if depth == 0
+                        && self
+                            .token_stream
+                            .get(i + 1)
+                            .is_some_and(|next: &Token<'a>| {
+                                next.token_type == TokenType::Identifier
+                            }) =>
+                {
+                    items.push(i)
+                }
+                _ => {}
+            }
+        }
+
+        let items_of = |token_type: TokenType| -> Vec<usize> {
+            items
+                .iter()
+                .copied()
+                .filter(|i: &usize| self.token_stream[*i].token_type == token_type)
+                .collect()
+        };
+        let (imports, types, enums, interfaces, funcs) = (
+            items_of(TokenType::Import),
+            items_of(TokenType::Type),
+            items_of(TokenType::Enum),
+            items_of(TokenType::Interface),
+            items_of(TokenType::Func),
+        );
+
+        for i in imports {
+            self.seek(i);
+            self.parse_import();
+        }
+
+        // Names go first, so fields and payloads can refer to types declared after them
+        for &i in types.iter().chain(&enums).chain(&interfaces) {
+            self.seek(i + 1);
+
+            self.search_node(self.current_token.slice.to_owned(), false, 2)
+                .0
+                .is_ok()
+                .then(|| {
+                    self.update_error_handler();
+                    self.error_handler.throw_name_already_used(2);
+                });
+
+            self.update_error_handler();
+
+            let location: Location<'a> = self.error_handler.location();
+            let name: String = self.current_token.slice.to_owned();
+            let ty_params: Vec<String> = self.parse_ty_params();
+
+            self.current_scope
+                .type_vec
+                .push(TypeNode::new(name, ty_params, vec![], location));
+        }
+
+        // Types list the interfaces they implement, so those come first
+        for i in interfaces {
+            self.seek(i);
+            self.parse_interface();
+        }
+
+        for i in enums {
+            self.seek(i);
+            self.parse_enum();
+        }
+
+        for i in types {
+            self.seek(i);
+            self.parse_type(false);
+        }
+
+        for i in funcs {
+            self.seek(i + 1);
+
+            let define_node: DefineFunctionNode<'a> = self.parse_define_function_node();
+
+            self.current_scope
+                .func_vec
+                .push(FunctionNode::new(define_node, ScopeNode::new()));
+        }
+
+        self.tok_i = 0;
+        self.current_token = self.token_stream[0];
+    }
+
+    // Makes the token at `i` the current one
+    fn seek(&mut self, i: usize) {
+        self.tok_i = i + 1;
+        self.current_token = self.token_stream[i];
+    }
+
+    // Skips from the current token to the `}` closing the next block
+    fn skip_block(&mut self) {
+        while self.current_token.token_type != TokenType::OpenBrace {
+            self.next(1);
+        }
+
+        let mut depth: usize = 0;
+
+        loop {
+            match self.current_token.token_type {
+                TokenType::OpenBrace => depth += 1,
+                TokenType::CloseBrace => depth -= 1,
+                _ => {}
+            }
+
+            if depth == 0 {
+                break;
+            }
+
+            self.next(1);
+        }
+    }
+
+    // Whether a name found in scope is the entry registered by `collect_declarations` for the
+    // declaration being parsed, rather than a different item with the same name
+    fn is_declared_here(&self, location: &Location<'a>) -> bool {
+        location.token == self.current_token
+    }
+
     // * Flow functions
 
     fn back(&mut self) {
@@ -127,696 +342,3706 @@ impl<'a> Parser<'a> {
     // * Type functions
 
     fn parse_ty(&mut self) -> Either<VarType, ArrayVarType> {
-        if self.peek().token_type == TokenType::OpenBracket {
-            let tmp: ArrayVarType = self.get_array_ty().unwrap();
-
-            self.next(1);
+        let ty: VarType = self.get_ty().unwrap();
 
-            Right(tmp)
+        if self.peek().token_type == TokenType::OpenBracket {
+            Right(self.get_array_ty(ty).unwrap())
         } else {
-            Left(self.get_ty().unwrap())
+            Left(ty)
         }
     }
 
     fn get_ty(&mut self) -> Option<VarType> {
         match self.current_token.token_type {
-            TokenType::IntType => Some(VarType::Int),
-            TokenType::FloatType => Some(VarType::Float),
+            TokenType::IntType => Some(VarType::Int {
+                ty: IntType::from_name(self.current_token.slice).unwrap(),
+            }),
+            TokenType::FloatType => Some(VarType::Float {
+                ty: FloatType::from_name(self.current_token.slice).unwrap(),
+            }),
             TokenType::StringType => Some(VarType::String),
             TokenType::BoolType => Some(VarType::Bool),
             TokenType::CharType => Some(VarType::Char),
-            _ => {
-                self.search_node(self.current_token.slice.to_owned(), true, 2)
-                    .0
-                    .unwrap();
-
-                Some(VarType::Type {
+            TokenType::Func => Some(self.parse_func_ty()),
+            TokenType::RangeType => Some(self.parse_range_ty()),
+            TokenType::OpenParen => Some(self.parse_tuple_ty()),
+            // `map` isn't a keyword, so it can still name functions and variables
+            TokenType::Identifier if self.current_token.slice == "map" => Some(self.parse_map_ty()),
+            _ if self
+                .ty_params
+                .iter()
+                .any(|param| param == self.current_token.slice) =>
+            {
+                Some(VarType::Generic {
                     name: self.current_token.slice.to_owned(),
                 })
             }
+            _ => {
+                let name: String = self.current_token.slice.to_owned();
+                let idx: usize = self.search_node(name.clone(), true, 2).0.unwrap();
+                let args: Vec<Either<VarType, ArrayVarType>> = self.parse_ty_args(idx);
+
+                Some(VarType::Type { name, args })
+            }
         }
     }
 
-    fn get_ty_from_val(&mut self, token: Token<'a>) -> Option<VarType> {
-        match token.token_type {
-            TokenType::Number | TokenType::NegativeNumber => Some(VarType::Int),
-            TokenType::Float | TokenType::NegativeFloat => Some(VarType::Float),
-            TokenType::String => Some(VarType::String),
-            TokenType::Bool => Some(VarType::Bool),
-            TokenType::Char => Some(VarType::Char),
-            TokenType::Identifier => Some({
-                match self.peek().token_type {
-                    TokenType::OpenParen => {
-                        let found_node_idx: (Result<usize, usize>, bool) =
-                            self.search_node(token.slice.to_owned(), true, 1);
-
-                        self.current_scope.func_vec[found_node_idx.0.unwrap()]
-                            .clone()
-                            .define_node
-                            .ret_ty?
-                            .unwrap_left()
-                    }
-                    TokenType::OpenBrace => {
-                        let found_node_idx: (Result<usize, usize>, bool) =
-                            self.search_node(token.slice.to_owned(), true, 2);
+    // `func(int, string): bool`, starts on `func` and ends on the last token of the type
+    fn parse_func_ty(&mut self) -> VarType {
+        let mut args: Vec<Either<VarType, ArrayVarType>> = vec![];
 
-                        let found_node: TypeNode =
-                            self.current_scope.type_vec[found_node_idx.0.unwrap()].clone();
-
-                        VarType::Type {
-                            name: found_node.name,
-                        }
-                    }
-                    _ => {
-                        let found_node_idx: (Result<usize, usize>, bool) =
-                            self.search_node(token.slice.to_owned(), true, 0);
-
-                        self.current_scope.var_vec[found_node_idx.0.unwrap()]
-                            .clone()
-                            .1
-                             .1
-                            .unwrap_left()
-                    }
-                }
-            }),
-            _ => {
-                self.update_error_handler();
-                self.error_handler.throw_name_not_defined(2);
+        self.next(1);
 
-                None
-            }
+        if self.current_token.token_type != TokenType::OpenParen {
+            self.update_error_handler();
+            self.error_handler.throw_unkown_token();
         }
-    }
 
-    fn get_array_ty(&mut self) -> Option<ArrayVarType> {
-        let type_token: Token<'a> = self.current_token;
-        let type_name: String = self.current_token.slice.to_owned();
+        if self.peek().token_type == TokenType::CloseParen {
+            self.next(1);
+        }
 
-        self.next(2);
+        while self.current_token.token_type != TokenType::CloseParen {
+            self.next(1);
 
-        let init_num: isize = self.current_token.slice.parse::<isize>().unwrap();
+            args.push(self.parse_ty());
 
-        match type_token.token_type {
-            TokenType::IntType => Some(ArrayVarType::Int { init_num }),
-            TokenType::FloatType => Some(ArrayVarType::Float { init_num }),
-            TokenType::StringType => Some(ArrayVarType::String { init_num }),
-            TokenType::BoolType => Some(ArrayVarType::Bool { init_num }),
-            TokenType::CharType => Some(ArrayVarType::Char { init_num }),
-            _ => {
-                self.search_node(type_name, true, 2).0.unwrap();
+            self.next(1);
 
-                Some(ArrayVarType::Type {
-                    name: self.current_token.slice.to_owned(),
-                    init_num,
-                })
+            if !matches!(
+                self.current_token.token_type,
+                TokenType::Comma | TokenType::CloseParen
+            ) {
+                self.update_error_handler();
+                self.error_handler.throw_unkown_token();
             }
         }
-    }
 
-    // * Parser
+        let ret_ty: Option<Box<Either<VarType, ArrayVarType>>> =
+            if self.peek().token_type == TokenType::Colon {
+                self.next(2);
 
-    fn parse_list(&mut self, token: Token<'a>) -> Nodes<'a> {
-        match token.token_type {
-            TokenType::Semicolon => Nodes::Eol,
-            TokenType::Number
-            | TokenType::Float
-            | TokenType::String
-            | TokenType::Char
-            | TokenType::Bool
-            | TokenType::Identifier
-            | TokenType::NegativeFloat
-            | TokenType::NegativeNumber => match self.peek().token_type {
-                TokenType::Plus | TokenType::Minus | TokenType::Divide | TokenType::Multiply => {
-                    let mut tok_stream: Vec<Token<'a>> = vec![];
+                Some(Box::new(self.parse_ty()))
+            } else {
+                None
+            };
 
-                    loop {
-                        tok_stream.push(self.current_token);
+        VarType::Func { args, ret_ty }
+    }
 
-                        self.next(1);
+    // `(int, string)`, starts on `(` and ends on `)`
+    fn parse_tuple_ty(&mut self) -> VarType {
+        let open_token: Token<'a> = self.current_token;
+        let mut elems: Vec<Either<VarType, ArrayVarType>> = vec![];
 
-                        if self.current_token.token_type == TokenType::Semicolon
-                            || self.current_token.token_type == TokenType::Colon
-                            || self.current_token.token_type == TokenType::CloseBracket
-                        {
-                            self.back();
-                            break;
-                        }
-                    }
+        while self.current_token.token_type != TokenType::CloseParen {
+            self.next(1);
 
-                    return Nodes::ProcessedMathNode(math::process_math_node(
-                        tok_stream,
-                        self.get_line(self.current_token.line).to_owned(),
-                        self.file_name.clone(),
-                    ));
-                }
-                TokenType::OpenParen => {
-                    let id: usize = self
-                        .search_node(self.current_token.slice.to_owned(), true, 1)
-                        .0
-                        .unwrap();
+            elems.push(self.parse_ty());
 
-                    self.next(2);
+            self.next(1);
 
-                    let mut args_vec: Vec<Nodes<'a>> = vec![];
+            if !matches!(
+                self.current_token.token_type,
+                TokenType::Comma | TokenType::CloseParen
+            ) {
+                self.update_error_handler();
+                self.error_handler.throw_unkown_token();
+            }
+        }
 
-                    while self.current_token.token_type != TokenType::CloseParen {
-                        args_vec.push(self.parse_list(self.current_token));
+        if elems.len() < 2 {
+            self.current_token = open_token;
+            self.update_error_handler();
+            self.error_handler.throw_short_tuple();
+        }
 
-                        self.next(if self.peek().token_type == TokenType::Comma {
-                            2
-                        } else {
-                            1
-                        });
-                    }
+        VarType::Tuple { elems }
+    }
 
-                    self.next(1);
+    // `range<i32>`, a plain `range` is a range of `int`
+    fn parse_range_ty(&mut self) -> VarType {
+        if self.peek().token_type != TokenType::LessThan {
+            return VarType::Range { ty: IntType::I64 };
+        }
 
-                    return Nodes::CallFuncNode(CallFuncNode::new(id, args_vec));
-                }
-                TokenType::DoubleColon => todo!(),
-                _ => {
-                    let vec_to_search: u8 = if self.peek().token_type == TokenType::OpenBrace {
-                        2
-                    } else {
-                        0
-                    };
+        let name_token: Token<'a> = self.current_token;
+        let args: Vec<Either<VarType, ArrayVarType>> = self.parse_ty_list();
 
-                    let found_node: Result<usize, usize> = self
-                        .search_node(self.current_token.slice.to_owned(), false, vec_to_search)
-                        .0;
+        if let [Left(VarType::Int { ty })] = args.as_slice() {
+            return VarType::Range { ty: *ty };
+        }
 
-                    if found_node.is_err() {
-                        return Nodes::PrimitiveTypeNode(self.parse_primitive_type_node());
-                    }
+        self.current_token = name_token;
+        self.update_error_handler();
 
-                    match vec_to_search {
-                        0 => {
-                            let is_var_node: bool;
-
-                            let mut call_var_node: Nodes<'a> =
-                                if self.peek().token_type == TokenType::OpenBracket {
-                                    is_var_node = false;
-                                    Nodes::CallVarArrNode(self.parse_call_var_arr())
-                                } else {
-                                    is_var_node = true;
-                                    Nodes::CallVarNode(self.parse_call_var())
-                                };
-
-                            if self.peek().token_type == TokenType::Assign {
-                                if is_var_node {
-                                    call_var_node = Nodes::AssignToVarNode(
-                                        self.parse_assign_to_var(call_var_node),
-                                    );
-                                } else {
-                                    call_var_node = Nodes::AssignToVarArrNode(
-                                        self.parse_assign_to_var_arr(call_var_node),
-                                    );
-                                }
-                            }
+        match args.as_slice() {
+            [ty] => {
+                self.error_handler
+                    .throw_wrong_range_type(ty_to_string(&Some(ty.clone())));
 
-                            call_var_node
-                        }
-                        2 => Nodes::InitTypeNode(self.parse_type_init()),
-                        _ => Nodes::PrimitiveTypeNode(self.parse_primitive_type_node()),
-                    }
-                }
-            },
-            TokenType::Let | TokenType::Var | TokenType::Const => Nodes::VarNode(self.parse_var()),
-            TokenType::Func => Nodes::FunctionNode(self.parse_function()),
-            TokenType::Type => Nodes::TypeNode(self.parse_type()),
+                unreachable!()
+            }
             _ => {
-                self.update_error_handler();
-                self.error_handler.throw_unkown_token();
-                Nodes::NullNode
+                self.error_handler
+                    .throw_wrong_ty_arg_count("range", 1, args.len());
+
+                unreachable!()
             }
         }
     }
 
-    fn parse_primitive_type_node(&mut self) -> PrimitiveTypeNode<'a> {
-        PrimitiveTypeNode(self.current_token)
-    }
+    // `map<string, int>`
+    fn parse_map_ty(&mut self) -> VarType {
+        let name_token: Token<'a> = self.current_token;
+        let args: Vec<Either<VarType, ArrayVarType>> = self.parse_ty_list();
+        let last_token: Token<'a> = self.current_token;
 
-    // * Variables
+        self.current_token = name_token;
+        self.update_error_handler();
 
-    fn parse_value(&mut self, name: String, ty: Either<VarType, ArrayVarType>) -> ValueNode<'a> {
-        if self.current_token.token_type == TokenType::OpenBracket {
-            self.next(1);
+        let [key, val]: [Either<VarType, ArrayVarType>; 2] = match args.try_into() {
+            Ok(args) => args,
+            Err(args) => {
+                self.error_handler
+                    .throw_wrong_ty_arg_count("map", 2, args.len());
 
-            let mut index: isize = 0;
-            let mut value: Vec<ArrElem<'a>> = vec![];
+                unreachable!()
+            }
+        };
 
-            loop {
-                let val_ty: VarType = self.get_ty_from_val(self.current_token).unwrap();
+        if !matches!(
+            key,
+            Left(
+                VarType::Int { ty: _ }
+                    | VarType::String
+                    | VarType::Char
+                    | VarType::Bool
+                    | VarType::Generic { name: _ }
+            )
+        ) {
+            self.error_handler
+                .throw_wrong_key_type(ty_to_string(&Some(key.clone())));
+        }
 
-                if val_ty != ty.clone().unwrap_right().to_var_type() {
-                    self.update_error_handler();
-                    self.error_handler.throw_wrong_assign_type(
-                        &name,
-                        val_ty.to_string(),
-                        ty.clone().unwrap_right().to_var_type().to_string(),
-                    );
-                }
+        self.current_token = last_token;
 
-                if &index == ty.clone().unwrap_right().get_init_num() {
-                    self.update_error_handler();
-                    self.error_handler
-                        .throw_array_out_of_bounds(ty.clone().unwrap_right().get_init_num());
-                }
+        VarType::Map {
+            key: Box::new(key),
+            val: Box::new(val),
+        }
+    }
 
-                value.push(ArrElem(
-                    Box::new(self.parse_list(self.current_token)),
-                    index,
-                ));
+    // `<A, B>` after the name of a generic type or function, leaves the current token on `>`
+    fn parse_ty_params(&mut self) -> Vec<String> {
+        let mut ty_params: Vec<String> = vec![];
 
-                self.next(1);
+        if self.peek().token_type != TokenType::LessThan {
+            return ty_params;
+        }
 
-                if self.current_token.token_type == TokenType::Comma {
-                    index += 1;
-                    self.next(1);
-                } else if self.current_token.token_type == TokenType::CloseBracket {
-                    break;
-                }
-            }
+        self.next(2);
 
-            ValueNode(Either::Right(value), ty)
-        } else {
-            let val_ty: VarType = self.get_ty_from_val(self.current_token).unwrap();
+        loop {
+            let name: String = self.current_token.slice.to_owned();
 
-            if val_ty != ty.clone().unwrap_left() {
+            if ty_params.contains(&name) {
                 self.update_error_handler();
-                self.error_handler.throw_wrong_assign_type(
-                    &name,
-                    val_ty.to_string(),
-                    ty.clone().unwrap_left().to_string(),
-                );
+                self.error_handler.throw_name_already_used(4);
             }
-            ValueNode(
-                Either::Left(Box::new(self.parse_list(self.current_token))),
-                ty,
-            )
-        }
-    }
-
-    fn parse_var(&mut self) -> VarNode<'a> {
-        let is_mut: bool = self.current_token.token_type == TokenType::Var;
 
-        self.next(1);
+            ty_params.push(name);
 
-        let name: String = self.current_token.slice.to_owned();
+            self.next(1);
 
-        if name.chars().next().unwrap().is_numeric() {
-            self.update_error_handler();
-            self.error_handler.throw_cant_start_var_num();
-        };
+            match self.current_token.token_type {
+                TokenType::Comma => self.next(1),
+                TokenType::GreaterThan => break,
+                _ => {
+                    self.update_error_handler();
+                    self.error_handler.throw_unkown_token();
+                }
+            }
+        }
 
-        self.search_node(name.clone(), false, 0).0.is_ok().then(|| {
-            self.update_error_handler();
-            self.error_handler.throw_name_already_used(0);
-        });
+        ty_params
+    }
 
-        self.next(2);
+    // `<int, string>` after the name of a type, empty when there's none. `>>` is a single token,
+    // so the inner list of `Box<Pair<int, int>>` ends on it and leaves `split_gt` set for the
+    // outer one
+    fn parse_ty_list(&mut self) -> Vec<Either<VarType, ArrayVarType>> {
+        let mut args: Vec<Either<VarType, ArrayVarType>> = vec![];
 
-        let ty: Either<VarType, ArrayVarType> = self.parse_ty();
+        if self.peek().token_type != TokenType::LessThan {
+            return args;
+        }
 
         self.next(2);
 
-        let new_node: VarNode<'a> = VarNode(name.clone(), self.parse_value(name, ty), is_mut);
+        loop {
+            args.push(self.parse_ty());
 
-        self.current_scope.var_vec.push(new_node.clone());
+            if self.split_gt {
+                self.split_gt = false;
 
-        new_node
-    }
+                break;
+            }
 
-    fn parse_call_var(&mut self) -> CallVarNode<'a> {
-        let idk: (Result<usize, usize>, bool) =
-            self.search_node(self.current_token.slice.to_owned(), true, 0);
+            self.next(1);
 
-        let a: CallVarNode = CallVarNode(self.current_scope.var_vec[idk.0.unwrap()].clone());
+            match self.current_token.token_type {
+                TokenType::Comma => self.next(1),
+                TokenType::GreaterThan => break,
+                TokenType::StrictGreaterThan => {
+                    self.split_gt = true;
 
-        a
-    }
+                    break;
+                }
+                _ => {
+                    self.update_error_handler();
+                    self.error_handler.throw_unkown_token();
+                }
+            }
+        }
 
-    fn parse_index(&mut self) -> isize {
-        if self.current_token.token_type == TokenType::Identifier {
-            let node_to_parse: Box<Nodes<'a>> = if self.peek().token_type == TokenType::OpenBracket
-            {
-                // * Don't delete this variable.
-                let idk0: CallVarArrNode<'a> = self.parse_call_var_arr();
+        args
+    }
 
-                idk0.0 .0 .1 .0.unwrap_right()[idk0.1 as usize].0.clone()
-            } else {
-                self.parse_call_var().0 .1 .0.unwrap_left()
-            };
+    // Type arguments of the type at `idx`
+    fn parse_ty_args(&mut self, idx: usize) -> Vec<Either<VarType, ArrayVarType>> {
+        let name_token: Token<'a> = self.current_token;
+        let args: Vec<Either<VarType, ArrayVarType>> = self.parse_ty_list();
 
-            let unpacked_node: Token<'a> = node_to_parse.get_primitive().unwrap();
+        let ty: &TypeNode<'a> = &self.current_scope.type_vec[idx];
 
-            if unpacked_node.token_type != TokenType::Number {
-                let val_ty: VarType = self.get_ty_from_val(unpacked_node).unwrap();
+        if args.len() != ty.ty_params.len() {
+            let (name, expected): (String, usize) = (ty.name.clone(), ty.ty_params.len());
 
+            self.current_token = name_token;
+            self.update_error_handler();
+            self.error_handler
+                .throw_wrong_ty_arg_count(&name, expected, args.len());
+        }
+
+        args
+    }
+
+    // Type arguments of a generic type or function inferred from values, `ty_args` holds the
+    // ones found so far
+    fn resolve_ty_args(
+        &mut self,
+        name: &str,
+        ty_params: &[String],
+        ty_args: Vec<Option<Either<VarType, ArrayVarType>>>,
+        token: Token<'a>,
+    ) -> Vec<Either<VarType, ArrayVarType>> {
+        ty_args
+            .into_iter()
+            .zip(ty_params)
+            .map(|(ty_arg, ty_param)| {
+                ty_arg.unwrap_or_else(|| {
+                    self.current_token = token;
+                    self.update_error_handler();
+                    self.error_handler.throw_cant_infer_ty_arg(ty_param, name);
+
+                    unreachable!()
+                })
+            })
+            .collect()
+    }
+
+    // Signature of a call to a generic function, its type parameters are replaced by the types
+    // of the arguments. Literals are looked at last, so `max(small, 1)` and `max(1, small)`
+    // both use the type of `small`
+    fn instantiate_func(
+        &mut self,
+        func: DefineFunctionNode<'a>,
+        args: &[Nodes<'a>],
+        token: Token<'a>,
+    ) -> DefineFunctionNode<'a> {
+        if func.ty_params.is_empty() {
+            return func;
+        }
+
+        let mut ty_args: Vec<Option<Either<VarType, ArrayVarType>>> =
+            vec![None; func.ty_params.len()];
+        // Type parameters bound by a number literal, a later argument with a type the literal
+        // fits binds them instead, `f(1, byte)` takes `u8`
+        let mut from_literal: Vec<bool> = vec![false; func.ty_params.len()];
+
+        for (i, arg) in args.iter().enumerate() {
+            // A spread gives the whole variadic argument
+            let (arg, param_ty) = match (arg, func.arg_at(i)) {
+                (Nodes::NamedArgNode(arg), _) => match func.arg_index(&arg.name) {
+                    Some(idx) => (arg.val.as_ref(), func.args[idx].ty.clone()),
+                    None => continue,
+                },
+                (Nodes::SpreadNode(spread), Some((arg_node, _))) => {
+                    (spread.val.as_ref(), arg_node.ty.clone())
+                }
+                (arg, Some((_, param_ty))) => (arg, param_ty),
+                (_, None) => continue,
+            };
+
+            let Some(arg_ty) = arg.get_ty() else {
+                continue;
+            };
+
+            let mut found: Vec<Option<Either<VarType, ArrayVarType>>> =
+                vec![None; func.ty_params.len()];
+
+            infer_ty_args(&param_ty, &arg_ty, &func.ty_params, &mut found);
+
+            // Bindings that conflict with an earlier one are reported by the checker at this
+            // argument
+            for (j, found) in found.into_iter().enumerate() {
+                let Some(found) = found else {
+                    continue;
+                };
+
+                let refines: bool = match (&ty_args[j], &found) {
+                    (None, _) => true,
+                    (Some(Left(bound)), Left(found)) => {
+                        from_literal[j] && !arg.is_num_literal() && bound.literal_fits(found)
+                    }
+                    _ => false,
+                };
+
+                if refines {
+                    ty_args[j] = Some(found);
+                    from_literal[j] = arg.is_num_literal();
+                }
+            }
+        }
+
+        let ty_args: Vec<Either<VarType, ArrayVarType>> =
+            self.resolve_ty_args(&func.name, &func.ty_params, ty_args, token);
+
+        DefineFunctionNode {
+            args: func
+                .args
+                .iter()
+                .map(|arg: &ArgNode<'a>| {
+                    ArgNode::new(
+                        arg.name.clone(),
+                        substitute_ty(&arg.ty, &func.ty_params, &ty_args),
+                        arg.default.clone(),
+                    )
+                })
+                .collect(),
+            ret_ty: func
+                .ret_ty
+                .as_ref()
+                .map(|ret_ty| substitute_ty(ret_ty, &func.ty_params, &ty_args)),
+            ty_params: vec![],
+            ..func
+        }
+    }
+
+    // Starts on the last token of the element type
+    fn get_array_ty(&mut self, elem_ty: VarType) -> Option<ArrayVarType> {
+        let mut dims: Vec<Option<isize>> = vec![];
+
+        while self.peek().token_type == TokenType::OpenBracket {
+            self.next(2);
+
+            dims.push(
+                if self.current_token.token_type == TokenType::CloseBracket {
+                    None
+                } else {
+                    // Sizes are checked while parsing, so they can only be literals
+                    if !matches!(
+                        self.current_token.token_type,
+                        TokenType::Number | TokenType::NegativeNumber
+                    ) {
+                        self.update_error_handler();
+                        self.error_handler
+                            .throw_array_size_not_literal(self.current_token.slice);
+                    }
+
+                    let Ok(init_num) = self.current_token.slice.parse::<isize>() else {
+                        self.update_error_handler();
+                        self.error_handler
+                            .throw_array_size_too_large(self.current_token.slice);
+
+                        unreachable!()
+                    };
+
+                    if init_num < 0 {
+                        self.update_error_handler();
+                        self.error_handler
+                            .throw_negative_array_size(self.current_token.slice);
+                    }
+
+                    self.next(1);
+
+                    Some(init_num)
+                },
+            );
+        }
+
+        let init_num: Option<isize> = dims.pop().unwrap();
+
+        let ty: ArrayVarType = ArrayVarType::from_elem_ty(Left(elem_ty), init_num);
+
+        Some(
+            dims.into_iter()
+                .rev()
+                .fold(ty, |ty: ArrayVarType, init_num: Option<isize>| {
+                    ArrayVarType::Array {
+                        ty: Box::new(ty),
+                        init_num,
+                    }
+                }),
+        )
+    }
+
+    // * Parser
+
+    fn parse_list(&mut self, token: Token<'a>) -> Nodes<'a> {
+        match token.token_type {
+            TokenType::Semicolon => Nodes::Eol,
+            TokenType::Identifier if self.peek().token_type == TokenType::Colon => {
+                self.parse_labeled_loop()
+            }
+            TokenType::Number
+            | TokenType::Float
+            | TokenType::String
+            | TokenType::Char
+            | TokenType::Bool
+            | TokenType::Identifier
+            | TokenType::NegativeFloat
+            | TokenType::NegativeNumber
+            | TokenType::OpenParen
+            | TokenType::OpenBracket => self.parse_expr(),
+            TokenType::Func if self.peek().token_type == TokenType::OpenParen => self.parse_expr(),
+            TokenType::Let | TokenType::Var | TokenType::Const
+                if self.peek().token_type == TokenType::OpenParen =>
+            {
+                Nodes::DestructureNode(self.parse_destructure())
+            }
+            TokenType::Let | TokenType::Var | TokenType::Const => Nodes::VarNode(self.parse_var()),
+            TokenType::Func => Nodes::FunctionNode(self.parse_function()),
+            TokenType::Type => Nodes::TypeNode(self.parse_type(true)),
+            TokenType::Enum => Nodes::TypeNode(self.parse_enum()),
+            TokenType::Interface => Nodes::TypeNode(self.parse_interface()),
+            TokenType::Return => Nodes::ReturnNode(self.parse_return()),
+            TokenType::ReturnIf => Nodes::ReturnIfNode(self.parse_return_if()),
+            TokenType::BreakIf => Nodes::BreakIfNode(self.parse_break_if()),
+            TokenType::Break => Nodes::BreakNode(BreakNode::new(self.parse_loop_jump())),
+            TokenType::Continue => Nodes::ContinueNode(ContinueNode::new(self.parse_loop_jump())),
+            TokenType::If => Nodes::IfNode(self.parse_if()),
+            TokenType::While => Nodes::WhileNode(self.parse_while(None)),
+            TokenType::Do => Nodes::DoWhileNode(self.parse_do_while(None)),
+            TokenType::For => Nodes::ForNode(self.parse_for(None)),
+            TokenType::Loop => Nodes::LoopNode(self.parse_loop(None)),
+            TokenType::Switch => Nodes::SwitchNode(self.parse_switch()),
+            TokenType::Import => Nodes::ImportNode(self.parse_import()),
+            TokenType::Export => Nodes::ExportNode(self.parse_export()),
+            _ => {
+                self.update_error_handler();
+                self.error_handler.throw_unkown_token();
+                Nodes::NullNode
+            }
+        }
+    }
+
+    // * Expressions
+
+    fn is_operand(token_type: TokenType) -> bool {
+        matches!(
+            token_type,
+            TokenType::Number
+                | TokenType::Float
+                | TokenType::String
+                | TokenType::Char
+                | TokenType::Bool
+                | TokenType::Identifier
+                | TokenType::NegativeFloat
+                | TokenType::NegativeNumber
+                | TokenType::Func
+                | TokenType::OpenBracket
+        )
+    }
+
+    // Leaves the current token on the last token of the expression. `|` has the lowest
+    // precedence, `a + b | f` is `f(a + b)`, and a stage ends the pipeline unless it's
+    // wrapped in parentheses, `(x | f) * 2`
+    fn parse_expr(&mut self) -> Nodes<'a> {
+        let mut in_stream: Vec<Nodes<'a>> = vec![];
+        let mut depth: usize = 0;
+        // Set after `|`, the next operand is a pipeline stage
+        let mut piped: bool = false;
+        // Parentheses depth of the last stage while they're still open
+        let mut stage_depth: Option<usize> = None;
+        let step_follows: bool = std::mem::take(&mut self.step_follows);
+
+        loop {
+            if piped {
+                // The stage takes everything before `|` up to the enclosing `(`
+                let start: usize = if depth > 0 {
+                    Self::open_paren_pos(&in_stream) + 1
+                } else {
+                    0
+                };
+                let val: Nodes<'a> = self.build_expr(in_stream.split_off(start));
+
+                in_stream.push(self.parse_pipe_stage(val));
+
+                piped = false;
+                stage_depth = Some(depth);
+            } else if self.current_token.token_type == TokenType::OpenParen
+                && !self.is_tuple_start()
+            {
+                in_stream.push(Nodes::MathOpTypeNode(MathOpTypeNode::new(
+                    OpType::OpenParen,
+                )));
+                depth += 1;
+
+                self.next(1);
+                continue;
+            } else {
+                if !Self::is_operand(self.current_token.token_type)
+                    && self.current_token.token_type != TokenType::OpenParen
+                {
+                    self.update_error_handler();
+                    self.error_handler.throw_unkown_token_in_math_expr();
+                }
+
+                let operand: Nodes<'a> = self.parse_operand();
+                let operand: Nodes<'a> = self.parse_func_value_call(operand);
+                in_stream.push(self.parse_cast(operand));
+            }
+
+            while depth > 0 && self.peek().token_type == TokenType::CloseParen {
+                depth -= 1;
+                stage_depth = stage_depth.filter(|&stage_depth: &usize| stage_depth <= depth);
+
+                self.next(1);
+
+                if self.peek().token_type != TokenType::As {
+                    in_stream.push(Nodes::MathOpTypeNode(MathOpTypeNode::new(
+                        OpType::CloseParen,
+                    )));
+                    continue;
+                }
+
+                // `(a + b) as T` casts the whole parenthesized expression
+                let inner: Vec<Nodes<'a>> = in_stream.split_off(Self::open_paren_pos(&in_stream));
+                let expr: Nodes<'a> = self.build_expr(inner.into_iter().skip(1).collect());
+
+                in_stream.push(self.parse_cast(expr));
+            }
+
+            let peek_type: TokenType = self.peek().token_type;
+
+            if stage_depth == Some(depth)
+                && (math::get_op_type(peek_type).is_some()
+                    || matches!(
+                        peek_type,
+                        TokenType::NegativeNumber | TokenType::NegativeFloat | TokenType::As
+                    ))
+            {
+                self.next(1);
+                self.update_error_handler();
+                self.error_handler.throw_op_after_pipeline();
+            }
+
+            match peek_type {
+                TokenType::Next if step_follows && depth == 0 => break,
+                TokenType::Next => {
+                    piped = true;
+
+                    self.next(2);
+                }
+                // `a -1` is lexed as `a` followed by the number `-1`
+                TokenType::NegativeNumber | TokenType::NegativeFloat => {
+                    in_stream.push(Nodes::MathOpTypeNode(MathOpTypeNode::new(OpType::Plus)));
+
+                    self.next(1);
+                }
+                token_type => match math::get_op_type(token_type) {
+                    Some(op) => {
+                        in_stream.push(Nodes::MathOpTypeNode(MathOpTypeNode::new(op)));
+
+                        self.next(2);
+                    }
+                    None => break,
+                },
+            }
+        }
+
+        if depth > 0 {
+            self.next(1);
+            self.update_error_handler();
+            self.error_handler.throw_unkown_token_in_math_expr();
+        }
+
+        self.build_expr(in_stream)
+    }
+
+    // Whether the `(` at the current token starts a tuple, `(a, b)`, instead of grouping
+    fn is_tuple_start(&self) -> bool {
+        let mut depth: usize = 0;
+
+        for token in &self.token_stream[self.tok_i - 1..] {
+            match token.token_type {
+                TokenType::OpenParen | TokenType::OpenBracket | TokenType::OpenBrace => depth += 1,
+                TokenType::CloseParen | TokenType::CloseBracket | TokenType::CloseBrace => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        return false;
+                    }
+                }
+                TokenType::Comma if depth == 1 => return true,
+                _ => {}
+            }
+        }
+
+        false
+    }
+
+    // `(a, b)`, starts on `(` and ends on `)`
+    fn parse_tuple(&mut self) -> TupleNode<'a> {
+        let token: Token<'a> = self.current_token;
+        let mut elems: Vec<Nodes<'a>> = vec![];
+
+        self.next(1);
+
+        while self.current_token.token_type != TokenType::CloseParen {
+            elems.push(self.parse_list(self.current_token));
+
+            self.next(if self.peek().token_type == TokenType::Comma {
+                2
+            } else {
+                1
+            });
+        }
+
+        TupleNode::new(elems, token)
+    }
+
+    fn build_expr(&mut self, mut in_stream: Vec<Nodes<'a>>) -> Nodes<'a> {
+        if in_stream.len() == 1 {
+            return in_stream.pop().unwrap();
+        }
+
+        let mut out_stream: Vec<Nodes<'a>> =
+            self.resolve_op_methods(math::process_math_node(in_stream));
+
+        if out_stream.len() == 1 {
+            out_stream.pop().unwrap()
+        } else {
+            Nodes::ProcessedMathNode(ProcessedMathNode::new(out_stream))
+        }
+    }
+
+    // Operators with a value of a user type on the left call the method the type defines for
+    // them, `a + b` is `a.add(b)`. `==` only does when the type defines `eq`
+    fn resolve_op_methods(&mut self, node: ProcessedMathNode<'a>) -> Vec<Nodes<'a>> {
+        // Each operand as its own part of the output stream
+        let mut stack: Vec<Vec<Nodes<'a>>> = vec![];
+
+        let to_node = |mut nodes: Vec<Nodes<'a>>| match nodes.len() {
+            1 => nodes.pop().unwrap(),
+            _ => Nodes::ProcessedMathNode(ProcessedMathNode::new(nodes)),
+        };
+
+        for node in node.out_stream {
+            let Nodes::MathOpTypeNode(op) = node else {
+                stack.push(vec![node]);
+                continue;
+            };
+
+            let rhs: Vec<Nodes<'a>> = stack.pop().unwrap();
+            let mut lhs: Vec<Nodes<'a>> = stack.pop().unwrap();
+
+            let lhs_node: Nodes<'a> = to_node(lhs.clone());
+            let op_method: Option<OpMethodNode<'a>> = match lhs_node.get_ty() {
+                Some(Left(VarType::Type { name, args })) => {
+                    self.op_method(&op.op, &name, &args, lhs_node, to_node(rhs.clone()))
+                }
+                _ => None,
+            };
+
+            match op_method {
+                Some(op_method) => stack.push(vec![Nodes::OpMethodNode(op_method)]),
+                None => {
+                    lhs.extend(rhs);
+                    lhs.push(Nodes::MathOpTypeNode(op));
+
+                    stack.push(lhs);
+                }
+            }
+        }
+
+        stack.pop().unwrap()
+    }
+
+    fn op_method(
+        &mut self,
+        op: &OpType,
+        ty_name: &str,
+        ty_args: &[Either<VarType, ArrayVarType>],
+        lhs: Nodes<'a>,
+        rhs: Nodes<'a>,
+    ) -> Option<OpMethodNode<'a>> {
+        // Method, whether the operands are swapped and whether the result is negated
+        let (name, swap, negate): (&str, bool, bool) = match op {
+            OpType::Plus => ("add", false, false),
+            OpType::Minus => ("sub", false, false),
+            OpType::Multiply => ("mul", false, false),
+            OpType::Divide => ("div", false, false),
+            OpType::Modulo => ("rem", false, false),
+            OpType::Power => ("pow", false, false),
+            OpType::Equal => ("eq", false, false),
+            OpType::NotEqual => ("eq", false, true),
+            OpType::LessThan => ("lt", false, false),
+            OpType::GreaterThan => ("lt", true, false),
+            OpType::LessThanOrEqual => ("lt", true, true),
+            OpType::GreaterThanOrEqual => ("lt", false, true),
+            _ => return None,
+        };
+
+        let token: Token<'a> = lhs.get_token().unwrap_or(self.current_token);
+        let method: DefineFunctionNode<'a> = self.find_method(ty_name, ty_args, name)?;
+
+        // Comparisons are negated, so they have to give a `bool`
+        let is_comparison: bool = matches!(name, "eq" | "lt");
+        let fits_op: bool = method.args.len() == 2
+            && method.args[0].name == "self"
+            && match &method.ret_ty {
+                Some(ret_ty) => !is_comparison || *ret_ty == Left(VarType::Bool),
+                None => false,
+            };
+
+        if !fits_op {
+            self.current_token = token;
+            self.update_error_handler();
+            self.error_handler.throw_wrong_op_method(
+                op.to_string(),
+                name,
+                ty_name,
+                if is_comparison { "a bool" } else { "a value" },
+            );
+        }
+
+        let args: Vec<Nodes<'a>> = if swap { vec![rhs, lhs] } else { vec![lhs, rhs] };
+        let method: DefineFunctionNode<'a> = self.instantiate_func(method, &args, token);
+
+        Some(OpMethodNode::new(
+            op.clone(),
+            CallFuncNode::new(method, args, token),
+            negate,
+        ))
+    }
+
+    // Position of the `(` that matches a `)` right after the end of `in_stream`
+    fn open_paren_pos(in_stream: &[Nodes<'a>]) -> usize {
+        let mut depth: usize = 0;
+
+        for (i, node) in in_stream.iter().enumerate().rev() {
+            match node {
+                Nodes::MathOpTypeNode(op) if op.op == OpType::CloseParen => depth += 1,
+                Nodes::MathOpTypeNode(op) if op.op == OpType::OpenParen => {
+                    if depth == 0 {
+                        return i;
+                    }
+
+                    depth -= 1;
+                }
+                _ => {}
+            }
+        }
+
+        unreachable!()
+    }
+
+    // `val as T`, casts can be chained and only take scalar types
+    fn parse_cast(&mut self, mut val: Nodes<'a>) -> Nodes<'a> {
+        while self.peek().token_type == TokenType::As {
+            self.next(2);
+
+            let ty_token: Token<'a> = self.current_token;
+            let ty: VarType = self.get_ty().unwrap();
+
+            val = Nodes::CastNode(CastNode::new(Box::new(val), ty, ty_token));
+        }
+
+        val
+    }
+
+    // `(a, b)`, starts on `(` and ends on `)`
+    fn parse_call_args(&mut self) -> Vec<Nodes<'a>> {
+        let mut args_vec: Vec<Nodes<'a>> = vec![];
+
+        self.next(1);
+
+        while self.current_token.token_type != TokenType::CloseParen {
+            args_vec.push(self.parse_call_arg());
+
+            self.next(if self.peek().token_type == TokenType::Comma {
+                2
+            } else {
+                1
+            });
+        }
+
+        args_vec
+    }
+
+    // `val`, `...arr` or `name: val`
+    fn parse_call_arg(&mut self) -> Nodes<'a> {
+        if self.current_token.token_type != TokenType::Identifier
+            || self.peek().token_type != TokenType::Colon
+        {
+            return self.parse_spread_or(|parser: &mut Self| parser.parse_arg_val());
+        }
+
+        let token: Token<'a> = self.current_token;
+
+        self.next(2);
+
+        let val: Nodes<'a> = self.parse_arg_val();
+
+        Nodes::NamedArgNode(NamedArgNode::new(
+            token.slice.to_owned(),
+            Box::new(val),
+            token,
+        ))
+    }
+
+    // Map literals can't start an expression, `if a {` opens a block, but arguments are never
+    // followed by one
+    fn parse_arg_val(&mut self) -> Nodes<'a> {
+        if self.current_token.token_type != TokenType::OpenBrace {
+            return self.parse_list(self.current_token);
+        }
+
+        let name: &str = "the map literal";
+        let ty: Either<VarType, ArrayVarType> = self.infer_ty(name);
+
+        Nodes::MapNode(self.parse_map(name, &ty))
+    }
+
+    // `f` or `f(a, b)`, starts on the name and ends on its last token. `args` are passed
+    // before the ones written in the call
+    fn parse_call_func(&mut self, mut args: Vec<Nodes<'a>>) -> CallFuncNode<'a> {
+        let func_token: Token<'a> = self.current_token;
+        let id: usize = self
+            .search_node(self.current_token.slice.to_owned(), true, 1)
+            .0
+            .unwrap();
+        let define_node: DefineFunctionNode<'a> =
+            self.current_scope.func_vec[id].define_node.clone();
+
+        if self.peek().token_type == TokenType::OpenParen {
+            self.next(1);
+
+            args.extend(self.parse_call_args());
+        }
+
+        let define_node: DefineFunctionNode<'a> =
+            self.instantiate_func(define_node, &args, func_token);
+
+        CallFuncNode::new(define_node, args, func_token)
+    }
+
+    // The stage after `|`, a function or a variable holding one that gets `val` as its first
+    // argument, `x | f(2)` is `f(x, 2)`
+    fn parse_pipe_stage(&mut self, val: Nodes<'a>) -> Nodes<'a> {
+        if self.current_token.token_type != TokenType::Identifier {
+            self.update_error_handler();
+            self.error_handler.throw_invalid_pipeline_stage();
+        }
+
+        if self
+            .current_scope
+            .search_node(self.current_token.slice.to_owned(), 0)
+            .1
+        {
+            return Nodes::CallFuncNode(self.parse_call_func(vec![val]));
+        }
+
+        let callee: Nodes<'a> = Nodes::CallVarNode(self.parse_call_var());
+
+        if !matches!(
+            callee.get_ty(),
+            Some(Left(VarType::Func { args: _, ret_ty: _ }))
+        ) {
+            self.update_error_handler();
+            self.error_handler.throw_invalid_pipeline_stage();
+        }
+
+        let mut args: Vec<Nodes<'a>> = vec![val];
+
+        if self.peek().token_type == TokenType::OpenParen {
+            self.next(1);
+
+            args.extend(self.parse_call_args());
+        }
+
+        Nodes::CallFuncValueNode(CallFuncValueNode::new(Box::new(callee), args))
+    }
+
+    // Calls through `callee` while it's followed by `(`, like `add(1)(2)`
+    fn parse_func_value_call(&mut self, mut callee: Nodes<'a>) -> Nodes<'a> {
+        while self.peek().token_type == TokenType::OpenParen {
+            let callee_ty: Option<Either<VarType, ArrayVarType>> = callee.get_ty();
+
+            if !matches!(callee_ty, Some(Left(VarType::Func { args: _, ret_ty: _ }))) {
+                self.current_token = callee.get_token().unwrap_or(self.current_token);
+                self.update_error_handler();
+                self.error_handler
+                    .throw_not_callable(ty_to_string(&callee_ty));
+            }
+
+            self.next(1);
+
+            let args_vec: Vec<Nodes<'a>> = self.parse_call_args();
+
+            callee = Nodes::CallFuncValueNode(CallFuncValueNode::new(Box::new(callee), args_vec));
+        }
+
+        callee
+    }
+
+    fn parse_operand(&mut self) -> Nodes<'a> {
+        match self.peek().token_type {
+            // Variables holding functions are called through `parse_func_value_call`
+            TokenType::OpenParen
+                if self.current_token.token_type == TokenType::Identifier
+                    && self
+                        .current_scope
+                        .search_node(self.current_token.slice.to_owned(), 0)
+                        .1 =>
+            {
+                Nodes::CallFuncNode(self.parse_call_func(vec![]))
+            }
+            TokenType::DoubleColon if self.get_variants(self.current_token.slice).is_none() => {
+                self.parse_static_call()
+            }
+            TokenType::DoubleColon => Nodes::InitEnumNode(self.parse_enum_init()),
+            _ => {
+                if self.current_token.token_type == TokenType::Func {
+                    return Nodes::LambdaNode(self.parse_lambda());
+                }
+
+                if self.current_token.token_type == TokenType::OpenParen {
+                    return Nodes::TupleNode(self.parse_tuple());
+                }
+
+                if self.current_token.token_type == TokenType::OpenBracket {
+                    return Nodes::ValueNode(self.parse_arr_literal());
+                }
+
+                if self.current_token.token_type == TokenType::String
+                    && self.current_token.slice.contains(['{', '}'])
+                {
+                    return Nodes::InterpolationNode(self.parse_interpolation());
+                }
+
+                if self.current_token.token_type != TokenType::Identifier {
+                    return Nodes::PrimitiveTypeNode(self.parse_primitive_type_node());
+                }
+
+                // `name {` is only a type initialization when `name` is a type, otherwise it's
+                // a variable followed by a block, like in `if done {`
+                let vec_to_search: u8 = if self.peek().token_type == TokenType::OpenBrace
+                    && self
+                        .current_scope
+                        .search_node(self.current_token.slice.to_owned(), 2)
+                        .0
+                        .is_ok()
+                {
+                    2
+                } else {
+                    0
+                };
+
+                let idx: usize = self
+                    .search_node(self.current_token.slice.to_owned(), true, vec_to_search)
+                    .0
+                    .unwrap();
+                let is_map: bool = vec_to_search == 0
+                    && matches!(
+                        self.current_scope.var_vec[idx].1 .1,
+                        Left(VarType::Map { key: _, val: _ })
+                    );
+                let is_str: bool = vec_to_search == 0
+                    && matches!(self.current_scope.var_vec[idx].1 .1, Left(VarType::String));
+
+                match vec_to_search {
+                    0 if self.peek().token_type == TokenType::Dot => {
+                        let var: CallVarNode<'a> = self.parse_call_var();
+
+                        if var.0 .1 .1.is_right() {
+                            Nodes::CallArrFuncNode(self.parse_call_arr_func(var))
+                        } else if is_map {
+                            Nodes::CallMapFuncNode(self.parse_call_map_func(var))
+                        } else if is_str {
+                            Nodes::CallStrFuncNode(self.parse_call_str_func(var))
+                        } else {
+                            self.parse_call_field(Nodes::CallVarNode(var))
+                        }
+                    }
+                    0 if is_map && self.peek().token_type == TokenType::OpenBracket => {
+                        let map: CallMapNode<'a> = self.parse_call_map();
+
+                        if self.peek().token_type == TokenType::Assign {
+                            Nodes::AssignToMapNode(self.parse_assign_to_map(map))
+                        } else {
+                            Nodes::CallMapNode(map)
+                        }
+                    }
+                    0 if is_str && self.peek().token_type == TokenType::OpenBracket => {
+                        Nodes::CallStrNode(self.parse_call_str())
+                    }
+                    0 => {
+                        let is_var_node: bool;
+
+                        let mut call_var_node: Nodes<'a> =
+                            if self.peek().token_type == TokenType::OpenBracket {
+                                is_var_node = false;
+                                Nodes::CallVarArrNode(self.parse_call_var_arr())
+                            } else {
+                                is_var_node = true;
+                                Nodes::CallVarNode(self.parse_call_var())
+                            };
+
+                        if self.peek().token_type == TokenType::Assign {
+                            if is_var_node {
+                                call_var_node =
+                                    Nodes::AssignToVarNode(self.parse_assign_to_var(call_var_node));
+                            } else {
+                                call_var_node = Nodes::AssignToVarArrNode(
+                                    self.parse_assign_to_var_arr(call_var_node),
+                                );
+                            }
+                        }
+
+                        call_var_node
+                    }
+                    _ => Nodes::InitTypeNode(self.parse_type_init()),
+                }
+            }
+        }
+    }
+
+    fn parse_primitive_type_node(&mut self) -> PrimitiveTypeNode<'a> {
+        // Every integer type is checked against the value of its literals, so they have to
+        // have one
+        if matches!(
+            self.current_token.token_type,
+            TokenType::Number | TokenType::NegativeNumber
+        ) && self.current_token.slice.parse::<i128>().is_err()
+        {
+            self.update_error_handler();
+            self.error_handler
+                .throw_literal_too_large(self.current_token.slice);
+        }
+
+        PrimitiveTypeNode(self.current_token)
+    }
+
+    // * Variables
+
+    fn parse_value(&mut self, name: String, ty: Either<VarType, ArrayVarType>) -> ValueNode<'a> {
+        if self.current_token.token_type == TokenType::OpenBracket {
+            let arr_ty: ArrayVarType = match ty.clone() {
+                Right(arr_ty) => arr_ty,
+                Left(var_ty) => {
+                    let literal_ty: String = self.literal_ty_name(&name);
+
+                    self.update_error_handler();
+                    self.error_handler.throw_wrong_assign_type(
+                        &name,
+                        literal_ty,
+                        var_ty.to_string(),
+                    );
+
+                    unreachable!()
+                }
+            };
+            let open_token: Token<'a> = self.current_token;
+
+            self.next(1);
+
+            let mut index: isize = 0;
+            let mut value: Vec<ArrElem<'a>> = vec![];
+
+            if self.current_token.token_type == TokenType::CloseBracket {
+                if let (Some(_), VarType::Type { name, args: _ }) =
+                    (arr_ty.get_init_num(), arr_ty.to_var_type())
+                {
+                    self.current_token = open_token;
+                    self.update_error_handler();
+                    self.error_handler.throw_no_default_value(name);
+                }
+
+                return ValueNode(Right(value), ty);
+            }
+
+            loop {
+                let elem: Nodes<'a> = self.parse_arr_elem(&name, &arr_ty);
+
+                if index == 0 && self.peek().token_type == TokenType::Semicolon {
+                    self.next(2);
+
+                    let count: isize = self.parse_repeat_count();
+
+                    self.next(1);
+
+                    if arr_ty
+                        .get_init_num()
+                        .is_some_and(|init_num| init_num != count)
+                    {
+                        self.current_token = open_token;
+                        self.update_error_handler();
+                        self.error_handler
+                            .throw_wrong_array_size(arr_ty.get_init_num().unwrap(), count as usize);
+                    }
+
+                    return ValueNode(
+                        Left(Box::new(Nodes::ArrRepeatNode(ArrRepeatNode::new(
+                            Box::new(elem),
+                            count,
+                        )))),
+                        ty,
+                    );
+                }
+
+                value.push(ArrElem(Box::new(elem), index));
+
+                self.next(1);
+
+                if self.current_token.token_type == TokenType::Comma {
+                    index += 1;
+                    self.next(1);
+                }
+
+                if self.current_token.token_type == TokenType::CloseBracket {
+                    break;
+                }
+            }
+
+            let len: Option<isize> = ArrElem::literal_len(&value);
+
+            if let Some(init_num) = arr_ty
+                .get_init_num()
+                .filter(|&init_num| len != Some(init_num))
+            {
+                self.current_token = open_token;
+                self.update_error_handler();
+
+                match len {
+                    Some(len) => self
+                        .error_handler
+                        .throw_wrong_array_size(init_num, len as usize),
+                    None => self.error_handler.throw_unsized_spread(init_num),
+                }
+            }
+
+            ValueNode(Right(value), ty)
+        } else if self.current_token.token_type == TokenType::OpenBrace {
+            ValueNode(
+                Left(Box::new(Nodes::MapNode(self.parse_map(&name, &ty)))),
+                ty,
+            )
+        } else {
+            ValueNode(
+                Either::Left(Box::new(self.parse_list(self.current_token))),
+                ty,
+            )
+        }
+    }
+
+    // `{"a": 1, "b": 2}`, starts on `{` and ends on `}`
+    fn parse_map(&mut self, name: &str, ty: &Either<VarType, ArrayVarType>) -> MapNode<'a> {
+        let (key_ty, val_ty) = match ty {
+            Left(VarType::Map { key, val }) => (*key.clone(), *val.clone()),
+            ty => {
+                let literal_ty: String = self.literal_ty_name(name);
+
+                self.update_error_handler();
+                self.error_handler.throw_wrong_assign_type(
+                    name,
+                    literal_ty,
+                    ty_to_string(&Some(ty.clone())),
+                );
+
+                unreachable!()
+            }
+        };
+        let token: Token<'a> = self.current_token;
+        let mut entries: Vec<(Nodes<'a>, Nodes<'a>)> = vec![];
+
+        self.next(1);
+
+        while self.current_token.token_type != TokenType::CloseBrace {
+            // Literal keys written twice are caught now, the rest can only be found at runtime
+            if self.current_token.token_type != TokenType::Identifier
+                && entries.iter().any(|(key, _)| {
+                    matches!(key, Nodes::PrimitiveTypeNode(key) if key.0.slice == self.current_token.slice)
+                })
+            {
+                self.update_error_handler();
+                self.error_handler.throw_duplicate_key();
+            }
+
+            let key: Nodes<'a> = self.parse_value_node(name.to_owned(), key_ty.clone());
+
+            self.next(1);
+
+            if self.current_token.token_type != TokenType::Colon {
+                self.update_error_handler();
+                self.error_handler.throw_unkown_token();
+            }
+
+            self.next(1);
+
+            let val: Nodes<'a> = self.parse_value_node(name.to_owned(), val_ty.clone());
+
+            entries.push((key, val));
+
+            self.next(if self.peek().token_type == TokenType::Comma {
+                2
+            } else {
+                1
+            });
+        }
+
+        MapNode::new(entries, token)
+    }
+
+    // Types are checked later, only nested literals need the expected type while parsing
+    fn parse_arr_elem(&mut self, name: &str, arr_ty: &ArrayVarType) -> Nodes<'a> {
+        self.parse_spread_or(|parser: &mut Self| {
+            parser.parse_value_node(name.to_owned(), arr_ty.elem_ty())
+        })
+    }
+
+    // `...arr` where a spread can be used, otherwise the value parsed by `parse`
+    fn parse_spread_or(&mut self, parse: impl FnOnce(&mut Self) -> Nodes<'a>) -> Nodes<'a> {
+        if self.current_token.token_type != TokenType::Ellipsis {
+            return parse(self);
+        }
+
+        let token: Token<'a> = self.current_token;
+
+        self.next(1);
+
+        Nodes::SpreadNode(SpreadNode::new(Box::new(self.parse_expr()), token))
+    }
+
+    fn parse_repeat_count(&mut self) -> isize {
+        let count_token: Token<'a> = self.current_token;
+        let count: Nodes<'a> = self.parse_expr();
+
+        match count
+            .const_eval()
+            .and_then(|count: i128| isize::try_from(count).ok())
+        {
+            Some(count) if count >= 0 => count,
+            _ => {
+                self.current_token = count_token;
+                self.update_error_handler();
+                self.error_handler.throw_non_const_repeat_count();
+
+                0
+            }
+        }
+    }
+
+    // Computes the type of the value starting at the current token without consuming it,
+    // `parse_value` then parses and checks it against the inferred type
+    fn infer_ty(&mut self, name: &str) -> Either<VarType, ArrayVarType> {
+        let start_i: usize = self.tok_i;
+        let start_token: Token<'a> = self.current_token;
+
+        let ty: Option<Either<VarType, ArrayVarType>> = if self.current_token.token_type
+            == TokenType::OpenBracket
+        {
+            self.next(1);
+
+            if self.current_token.token_type == TokenType::CloseBracket {
+                self.current_token = start_token;
+                self.update_error_handler();
+                self.error_handler.throw_cant_infer_type(name);
+            }
+
+            let elem_ty: Either<VarType, ArrayVarType> = if matches!(
+                self.current_token.token_type,
+                TokenType::OpenBracket | TokenType::OpenBrace
+            ) {
+                let elem_ty: Either<VarType, ArrayVarType> = self.infer_ty(name);
+
+                self.parse_value(name.to_owned(), elem_ty.clone());
+
+                elem_ty
+            } else {
+                let elem_token: Token<'a> = self.current_token;
+                let elem: Nodes<'a> = self
+                    .parse_spread_or(|parser: &mut Self| parser.parse_list(parser.current_token));
+
+                let elem_ty: Option<Either<VarType, ArrayVarType>> = match &elem {
+                    Nodes::SpreadNode(spread) => spread
+                        .val
+                        .get_ty()
+                        .and_then(Either::right)
+                        .map(|arr_ty: ArrayVarType| arr_ty.elem_ty()),
+                    elem => elem.get_ty(),
+                };
+
+                elem_ty.unwrap_or_else(|| {
+                    self.current_token = elem_token;
+                    self.update_error_handler();
+                    self.error_handler.throw_cant_infer_type(name);
+
+                    unreachable!()
+                })
+            };
+
+            let init_num: Option<isize> = if self.peek().token_type == TokenType::Semicolon {
+                self.next(2);
+
+                Some(self.parse_repeat_count())
+            } else {
+                // Spreads add the length of the arrays they spread, the array is unsized
+                // when one of them is
+                self.tok_i = start_i;
+                self.current_token = start_token;
+
+                let unsized_ty: ArrayVarType = ArrayVarType::from_elem_ty(elem_ty.clone(), None);
+
+                match self.parse_value(name.to_owned(), Right(unsized_ty)).0 {
+                    Right(elems) => ArrElem::literal_len(&elems),
+                    Left(_) => unreachable!(),
+                }
+            };
+
+            Some(Right(ArrayVarType::from_elem_ty(elem_ty, init_num)))
+        } else if self.current_token.token_type == TokenType::OpenBrace {
+            // Maps take the types of their first entry
+            self.next(1);
+
+            if self.current_token.token_type == TokenType::CloseBrace {
+                self.current_token = start_token;
+                self.update_error_handler();
+                self.error_handler.throw_cant_infer_type(name);
+            }
+
+            let key: Option<Either<VarType, ArrayVarType>> =
+                self.parse_list(self.current_token).get_ty();
+
+            self.next(2);
+
+            let val: Option<Either<VarType, ArrayVarType>> = if matches!(
+                self.current_token.token_type,
+                TokenType::OpenBracket | TokenType::OpenBrace
+            ) {
+                Some(self.infer_ty(name))
+            } else {
+                self.parse_list(self.current_token).get_ty()
+            };
+
+            key.zip(val).map(|(key, val)| {
+                Left(VarType::Map {
+                    key: Box::new(key),
+                    val: Box::new(val),
+                })
+            })
+        } else {
+            let value: Nodes<'a> = self.parse_list(self.current_token);
+
+            value.get_ty()
+        };
+
+        self.tok_i = start_i;
+        self.current_token = start_token;
+
+        ty.unwrap_or_else(|| {
+            self.update_error_handler();
+            self.error_handler.throw_cant_infer_type(name);
+
+            unreachable!()
+        })
+    }
+
+    // Type of the array or map literal at the current token for diagnostics, empty ones
+    // have none
+    fn literal_ty_name(&mut self, name: &str) -> String {
+        match self.peek().token_type {
+            TokenType::CloseBracket => "empty array".to_owned(),
+            TokenType::CloseBrace => "empty map".to_owned(),
+            _ => ty_to_string(&Some(self.infer_ty(name))),
+        }
+    }
+
+    // `[1, 2]` used as a value, its type is inferred like the one of a `let` without a type
+    fn parse_arr_literal(&mut self) -> ValueNode<'a> {
+        let name: &str = "the array literal";
+        let ty: Either<VarType, ArrayVarType> = self.infer_ty(name);
+
+        self.parse_value(name.to_owned(), ty)
+    }
+
+    // Like `parse_value`, but returns the bare node when the value isn't an array literal
+    fn parse_value_node(&mut self, name: String, ty: Either<VarType, ArrayVarType>) -> Nodes<'a> {
+        let value: ValueNode<'a> = self.parse_value(name, ty);
+
+        match value.0 {
+            Left(node) => *node,
+            Right(_) => Nodes::ValueNode(value),
+        }
+    }
+
+    fn parse_var(&mut self) -> VarNode<'a> {
+        // Only `const` declarations are read-only
+        let is_mut: bool = self.current_token.token_type != TokenType::Const;
+
+        self.next(1);
+
+        let name: String = self.current_token.slice.to_owned();
+
+        if name.chars().next().unwrap().is_numeric() {
+            self.update_error_handler();
+            self.error_handler.throw_cant_start_var_num();
+        };
+
+        self.search_node(name.clone(), false, 0).0.is_ok().then(|| {
+            self.update_error_handler();
+            self.error_handler.throw_name_already_used(0);
+        });
+
+        // Without a `: type` annotation the type is inferred from the initializer
+        let ty: Either<VarType, ArrayVarType> = if self.peek().token_type == TokenType::Assign {
+            self.next(2);
+
+            self.infer_ty(&name)
+        } else {
+            self.next(2);
+
+            let ty: Either<VarType, ArrayVarType> = self.parse_ty();
+
+            self.next(2);
+
+            ty
+        };
+
+        let new_node: VarNode<'a> = VarNode(name.clone(), self.parse_value(name, ty), is_mut);
+
+        self.current_scope.var_vec.push(new_node.clone());
+
+        new_node
+    }
+
+    // `let (a, b) = val;`, `let (a, _): (int, string) = val;`
+    fn parse_destructure(&mut self) -> DestructureNode<'a> {
+        let is_mut: bool = self.current_token.token_type != TokenType::Const;
+        let first_token: Token<'a> = self.peek();
+        let mut names: Vec<String> = vec![];
+
+        self.next(2);
+
+        while self.current_token.token_type != TokenType::CloseParen {
+            let name: String = self.current_token.slice.to_owned();
+
+            if self.current_token.token_type != TokenType::Identifier {
+                self.update_error_handler();
+                self.error_handler.throw_unkown_token();
+            }
+
+            if name != "_" {
+                if names.contains(&name) {
+                    self.update_error_handler();
+                    self.error_handler.throw_name_already_used(0);
+                }
+
+                self.search_node(name.clone(), false, 0).0.is_ok().then(|| {
+                    self.update_error_handler();
+                    self.error_handler.throw_name_already_used(0);
+                });
+            }
+
+            names.push(name);
+
+            self.next(if self.peek().token_type == TokenType::Comma {
+                2
+            } else {
+                1
+            });
+        }
+
+        let label: String = format!("({})", names.join(", "));
+
+        let ty: Either<VarType, ArrayVarType> = if self.peek().token_type == TokenType::Assign {
+            self.next(2);
+
+            self.infer_ty(&label)
+        } else {
+            self.next(2);
+
+            let ty: Either<VarType, ArrayVarType> = self.parse_ty();
+
+            self.next(2);
+
+            ty
+        };
+
+        let elems: Vec<Either<VarType, ArrayVarType>> = match &ty {
+            Left(VarType::Tuple { elems }) if elems.len() == names.len() => elems.clone(),
+            ty => {
+                self.current_token = first_token;
+                self.update_error_handler();
+                self.error_handler
+                    .throw_wrong_destructure(ty_to_string(&Some(ty.clone())), names.len());
+
+                unreachable!()
+            }
+        };
+
+        let val: ValueNode<'a> = self.parse_value(label, ty);
+
+        for (name, ty) in names.iter().zip(elems) {
+            if name != "_" {
+                self.current_scope.var_vec.push(VarNode(
+                    name.clone(),
+                    ValueNode(Left(Box::new(Nodes::NullNode)), ty),
+                    is_mut,
+                ));
+            }
+        }
+
+        DestructureNode::new(names, val, is_mut)
+    }
+
+    fn parse_call_var(&mut self) -> CallVarNode<'a> {
+        let idk: (Result<usize, usize>, bool) =
+            self.search_node(self.current_token.slice.to_owned(), true, 0);
+
+        let a: CallVarNode = CallVarNode(
+            self.current_scope.var_vec[idk.0.unwrap()].clone(),
+            self.current_token,
+        );
+
+        a
+    }
+
+    fn parse_index(&mut self) -> (Nodes<'a>, Option<i128>) {
+        let index_token: Token<'a> = self.current_token;
+        let index: Nodes<'a> = self.parse_expr();
+
+        let index_ty: Option<Either<VarType, ArrayVarType>> = index.get_ty();
+
+        if !matches!(index_ty, Some(Left(VarType::Int { ty: _ }))) {
+            let last_token: Token<'a> = self.current_token;
+
+            self.current_token = index_token;
+            self.update_error_handler();
+            self.error_handler
+                .throw_cant_use_val_in_arr_call(ty_to_string(&index_ty));
+
+            self.current_token = last_token;
+        }
+
+        let const_index: Option<i128> = index.const_eval();
+
+        (index, const_index)
+    }
+
+    fn parse_call_var_arr(&mut self) -> CallVarArrNode<'a> {
+        let var_to_call: CallVarNode<'a> = self.parse_call_var();
+
+        let mut dim_ty: Option<ArrayVarType> = match var_to_call.0 .1 .1.clone() {
+            Right(arr_ty) => Some(arr_ty),
+            Left(_) => None,
+        };
+        let mut indexes: Vec<ArrIndex<'a>> = vec![];
+
+        while self.peek().token_type == TokenType::OpenBracket {
+            self.next(2);
+
+            let index_token: Token<'a> = self.current_token;
+            let (index, const_index): (Nodes<'a>, Option<i128>) = self.parse_index();
+
+            self.next(1);
+
+            let arr_ty: ArrayVarType = match dim_ty {
+                Some(arr_ty) => arr_ty,
+                None => {
+                    self.update_error_handler();
+                    self.error_handler.throw_not_an_array(&var_to_call.0 .0);
+
+                    unreachable!()
+                }
+            };
+
+            // Constant indexes are checked now, everything else is left to the runtime check.
+            // No array holds more than `isize::MAX` elements
+            let bounds_check: bool = match (const_index, arr_ty.get_init_num()) {
+                (Some(const_index), init_num) => {
+                    let len: i128 = init_num.unwrap_or(isize::MAX) as i128;
+
+                    if !(0..len).contains(&const_index) {
+                        self.current_token = index_token;
+                        self.update_error_handler();
+                        self.error_handler
+                            .throw_cant_use_num_array(var_to_call.0 .0.as_str(), const_index);
+                    }
+
+                    init_num.is_none()
+                }
+                (None, _) => true,
+            };
+
+            dim_ty = arr_ty.elem_ty().right();
+            indexes.push(ArrIndex::new(Box::new(index), bounds_check));
+        }
+
+        CallVarArrNode(var_to_call, indexes)
+    }
+
+    fn parse_call_arr_func(&mut self, var: CallVarNode<'a>) -> CallArrFuncNode<'a> {
+        let arr_ty: ArrayVarType = match var.0 .1 .1.clone() {
+            Right(arr_ty) => arr_ty,
+            Left(_) => {
+                self.update_error_handler();
+                self.error_handler.throw_not_an_array(&var.0 .0);
+
+                unreachable!()
+            }
+        };
+
+        self.next(2);
+
+        let func: ArrFunc<'a> = match self.current_token.slice {
+            "len" => {
+                self.next(2);
+
+                ArrFunc::Len
+            }
+            "push" | "pop" => {
+                if !arr_ty.is_unsized() {
+                    self.update_error_handler();
+                    self.error_handler.throw_cant_resize_array(&var.0 .0);
+                }
+
+                if !var.0 .2 {
+                    self.update_error_handler();
+                    self.error_handler.throw_cant_mutate_var(&var.0 .0);
+                }
+
+                if self.current_token.slice == "pop" {
+                    self.next(2);
+
+                    ArrFunc::Pop
+                } else {
+                    self.next(2);
+
+                    let val: Box<Nodes<'a>> =
+                        Box::new(self.parse_value_node(var.0 .0.clone(), arr_ty.elem_ty()));
+
+                    self.next(1);
+
+                    ArrFunc::Push(val)
+                }
+            }
+            _ => {
+                self.update_error_handler();
+                self.error_handler.throw_unkown_array_func();
+
+                unreachable!()
+            }
+        };
+
+        CallArrFuncNode(var, func)
+    }
+
+    // `map[key]`, leaves the current token on `]`
+    fn parse_call_map(&mut self) -> CallMapNode<'a> {
+        let var: CallVarNode<'a> = self.parse_call_var();
+        let (key_ty, _) = Self::map_tys(&var);
+
+        self.next(2);
+
+        let key: Nodes<'a> = self.parse_value_node(var.0 .0.clone(), key_ty);
+
+        self.next(1);
+
+        if self.current_token.token_type != TokenType::CloseBracket {
+            self.update_error_handler();
+            self.error_handler.throw_unkown_token();
+        }
+
+        CallMapNode(var, Box::new(key))
+    }
+
+    fn parse_assign_to_map(&mut self, map: CallMapNode<'a>) -> AssignToMapNode<'a> {
+        if !map.0 .0 .2 {
+            self.update_error_handler();
+            self.error_handler.throw_cant_mutate_var(&map.0 .0 .0);
+        }
+
+        self.next(2);
+
+        let (_, val_ty) = Self::map_tys(&map.0);
+        let val: Box<Nodes<'a>> = Box::new(self.parse_value_node(map.0 .0 .0.clone(), val_ty));
+
+        AssignToMapNode(map, val)
+    }
+
+    // `map.len()` and `map.remove(key)`
+    fn parse_call_map_func(&mut self, var: CallVarNode<'a>) -> CallMapFuncNode<'a> {
+        let (key_ty, _) = Self::map_tys(&var);
+
+        self.next(2);
+
+        let func: MapFunc<'a> = match self.current_token.slice {
+            "len" => {
+                self.next(2);
+
+                MapFunc::Len
+            }
+            "remove" => {
+                if !var.0 .2 {
+                    self.update_error_handler();
+                    self.error_handler.throw_cant_mutate_var(&var.0 .0);
+                }
+
+                self.next(2);
+
+                let key: Box<Nodes<'a>> = Box::new(self.parse_value_node(var.0 .0.clone(), key_ty));
+
+                self.next(1);
+
+                MapFunc::Remove(key)
+            }
+            _ => {
+                self.update_error_handler();
+                self.error_handler.throw_unkown_map_func();
+
+                unreachable!()
+            }
+        };
+
+        CallMapFuncNode(var, func)
+    }
+
+    // Key and value types of a map variable
+    fn map_tys(
+        var: &CallVarNode<'a>,
+    ) -> (Either<VarType, ArrayVarType>, Either<VarType, ArrayVarType>) {
+        match &var.0 .1 .1 {
+            Left(VarType::Map { key, val }) => (*key.clone(), *val.clone()),
+            _ => unreachable!(),
+        }
+    }
+
+    // `str[i]` or `str[a..<b]`, ends on `]`
+    fn parse_call_str(&mut self) -> CallStrNode<'a> {
+        let var: CallVarNode<'a> = self.parse_call_var();
+
+        self.next(2);
+
+        let index_token: Token<'a> = self.current_token;
+        let index: Nodes<'a> = self.parse_expr();
+        let index_ty: Option<Either<VarType, ArrayVarType>> = index.get_ty();
+
+        if !matches!(
+            index_ty,
+            Some(Left(VarType::Int { ty: _ } | VarType::Range { ty: _ }))
+        ) {
+            self.current_token = index_token;
+            self.update_error_handler();
+            self.error_handler
+                .throw_wrong_str_index(ty_to_string(&index_ty));
+        }
+
+        self.next(1);
+
+        if self.current_token.token_type != TokenType::CloseBracket {
+            self.update_error_handler();
+            self.error_handler.throw_unkown_token();
+        }
+
+        if self.peek().token_type == TokenType::Assign {
+            self.next(1);
+            self.update_error_handler();
+            self.error_handler.throw_cant_assign_to_str_index();
+        }
+
+        CallStrNode(var, Box::new(index))
+    }
+
+    fn parse_call_str_func(&mut self, var: CallVarNode<'a>) -> CallStrFuncNode<'a> {
+        self.next(2);
+
+        let func: StrFunc = match self.current_token.slice {
+            "len" => {
+                self.next(2);
+
+                StrFunc::Len
+            }
+            _ => {
+                self.update_error_handler();
+                self.error_handler.throw_unkown_str_func();
+
+                unreachable!()
+            }
+        };
+
+        CallStrFuncNode(var, func)
+    }
+
+    // `"a {b} c"`, the expression in each pair of braces is lexed and parsed on its own
+    fn parse_interpolation(&mut self) -> InterpolationNode<'a> {
+        let token: Token<'a> = self.current_token;
+        let end: usize = token.slice.len() - 1;
+        let mut parts: Vec<StrPart<'a>> = vec![];
+        let mut text: String = String::new();
+        // Starts after the opening quote
+        let mut i: usize = 1;
+
+        while i < end {
+            let rest: &'a str = &token.slice[i..end];
+
+            if rest.starts_with("{{") || rest.starts_with("}}") {
+                text.push_str(&rest[..1]);
+                i += 2;
+                continue;
+            }
+
+            match rest.chars().next().unwrap() {
+                '{' => {
+                    let Some(len) = Self::interpolation_len(rest) else {
+                        self.current_token = Self::str_token(token, i, i + 1);
+                        self.update_error_handler();
+                        self.error_handler.throw_unclosed_interpolation();
+
+                        unreachable!()
+                    };
+
+                    if !text.is_empty() {
+                        parts.push(StrPart::Text(std::mem::take(&mut text)));
+                    }
+
+                    parts.push(StrPart::Expr(Box::new(self.parse_interpolated_expr(
+                        token,
+                        i + 1,
+                        i + len - 1,
+                    ))));
+
+                    i += len;
+                }
+                '}' => {
+                    self.current_token = Self::str_token(token, i, i + 1);
+                    self.update_error_handler();
+                    self.error_handler.throw_unclosed_interpolation();
+                }
+                c => {
+                    text.push(c);
+                    i += c.len_utf8();
+                }
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(StrPart::Text(text));
+        }
+
+        self.current_token = token;
+
+        InterpolationNode::new(parts, token)
+    }
+
+    // Length of the `{...}` at the start of `rest`, braces inside it have to be balanced
+    fn interpolation_len(rest: &str) -> Option<usize> {
+        let mut depth: usize = 0;
+
+        for (i, c) in rest.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        return Some(i + 1);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    // The part of the string token between `start` and `end`, where it is in the file
+    fn str_token(token: Token<'a>, start: usize, end: usize) -> Token<'a> {
+        let before: &str = &token.slice[..start];
+
+        Token {
+            line: token.line + before.matches('\n').count(),
+            column: match before.rfind('\n') {
+                Some(line_start) => start - line_start - 1,
+                None => token.column + start,
+            },
+            token_type: token.token_type,
+            slice: &token.slice[start..end],
+            span: Span {
+                start: token.span.start + start,
+                end: token.span.start + end,
+            },
+        }
+    }
+
+    // Parses the expression between `start` and `end` of the string token as if it was the
+    // whole token stream
+    fn parse_interpolated_expr(&mut self, token: Token<'a>, start: usize, end: usize) -> Nodes<'a> {
+        let mut tokens: Vec<Token<'a>> = vec![];
+
+        for (token_type, span) in TokenType::lexer(&token.slice[start..end]).spanned() {
+            let mut inner: Token<'a> = Self::str_token(token, start + span.start, start + span.end);
+
+            match token_type {
+                Ok(token_type) if token_type.is_trivia() => {}
+                Ok(token_type) => {
+                    inner.token_type = token_type;
+                    tokens.push(inner);
+                }
+                Err(_) => {
+                    self.current_token = inner;
+                    self.update_error_handler();
+                    self.error_handler.throw_unkown_token();
+                }
+            }
+        }
+
+        if tokens.is_empty() {
+            self.current_token = Self::str_token(token, start - 1, end + 1);
+            self.update_error_handler();
+            self.error_handler.throw_empty_interpolation();
+        }
+
+        let outer_stream: Vec<Token<'a>> = std::mem::replace(&mut self.token_stream, tokens);
+        let outer_i: usize = std::mem::replace(&mut self.tok_i, 0);
+
+        self.next(1);
+
+        let expr: Nodes<'a> = self.parse_expr();
+
+        if self.tok_i < self.token_stream.len() {
+            self.next(1);
+            self.update_error_handler();
+            self.error_handler.throw_unkown_token_in_math_expr();
+        }
+
+        self.token_stream = outer_stream;
+        self.tok_i = outer_i;
+
+        expr
+    }
+
+    // Leaves the current token on the last field name
+    fn parse_call_field(&mut self, mut obj: Nodes<'a>) -> Nodes<'a> {
+        while self.peek().token_type == TokenType::Dot {
+            self.next(2);
+
+            // Fields of generic types take the type arguments of the value
+            let field_ty: Option<Either<VarType, ArrayVarType>> = match obj.get_ty() {
+                Some(Left(VarType::Type { name, args })) => self
+                    .current_scope
+                    .type_vec
+                    .iter()
+                    .find(|ty: &&TypeNode| ty.name == name)
+                    .and_then(|ty: &TypeNode| {
+                        ty.args
+                            .iter()
+                            .find(|arg: &&TypeArgNode| arg.name == self.current_token.slice)
+                            .and_then(|arg: &TypeArgNode| arg.val.clone().left())
+                            .map(|field_ty| substitute_ty(&field_ty, &ty.ty_params, &args))
+                    }),
+                _ => None,
+            };
+
+            match field_ty {
+                Some(field_ty) => {
+                    obj = Nodes::CallFieldNode(CallFieldNode(
+                        Box::new(obj),
+                        self.current_token.slice.to_owned(),
+                        field_ty,
+                    ))
+                }
+                None if self.peek().token_type == TokenType::OpenParen => {
+                    obj = Nodes::CallMethodNode(self.parse_call_method(obj));
+                }
+                None => {
+                    self.update_error_handler();
+                    self.error_handler
+                        .throw_no_field(ty_to_string(&obj.get_ty()));
+                }
+            }
+        }
+
+        obj
+    }
+
+    // `obj.method(args)`, `obj` is passed as `self`. Starts on the name and ends on `)`
+    fn parse_call_method(&mut self, obj: Nodes<'a>) -> CallFuncNode<'a> {
+        let token: Token<'a> = self.current_token;
+        let obj_ty: Option<Either<VarType, ArrayVarType>> = obj.get_ty();
+
+        let (ty_name, method): (String, Option<DefineFunctionNode<'a>>) = match &obj_ty {
+            Some(Left(VarType::Type { name, args })) => {
+                (name.clone(), self.find_method(name, args, token.slice))
+            }
+            _ => (String::new(), None),
+        };
+
+        let Some(method) = method else {
+            self.update_error_handler();
+            self.error_handler.throw_no_method(ty_to_string(&obj_ty));
+
+            unreachable!()
+        };
+
+        if method
+            .args
+            .first()
+            .is_none_or(|arg: &ArgNode<'a>| arg.name != "self")
+        {
+            self.update_error_handler();
+            self.error_handler.throw_static_method_call(&ty_name);
+        }
+
+        self.next(1);
+
+        let mut args: Vec<Nodes<'a>> = vec![obj];
+        args.extend(self.parse_call_args());
+
+        let method: DefineFunctionNode<'a> = self.instantiate_func(method, &args, token);
+
+        CallFuncNode::new(method, args, token)
+    }
+
+    // `Type::method(args)` calls a method without a value of the type, `self` is then passed
+    // like any other argument
+    fn parse_static_call(&mut self) -> Nodes<'a> {
+        let idx: usize = self
+            .search_node(self.current_token.slice.to_owned(), true, 2)
+            .0
+            .unwrap();
+        let ty: TypeNode<'a> = self.current_scope.type_vec[idx].clone();
+
+        self.next(2);
+
+        let token: Token<'a> = self.current_token;
+
+        let Some(method) = ty.method(token.slice) else {
+            self.update_error_handler();
+            self.error_handler.throw_no_method(ty.name);
+
+            unreachable!()
+        };
+
+        // The type parameters of a generic type are inferred from the arguments, like the ones
+        // of the method
+        let method: DefineFunctionNode<'a> = DefineFunctionNode {
+            ty_params: ty
+                .ty_params
+                .iter()
+                .chain(&method.ty_params)
+                .cloned()
+                .collect(),
+            ..method
+        };
+
+        let mut args: Vec<Nodes<'a>> = vec![];
+
+        if self.peek().token_type == TokenType::OpenParen {
+            self.next(1);
+
+            args = self.parse_call_args();
+        }
+
+        let call: CallFuncNode<'a> =
+            CallFuncNode::new(self.instantiate_func(method, &args, token), args, token);
+
+        // Methods of interfaces only have a signature, the call goes to the one of the value
+        if ty.methods.is_some() {
+            Nodes::CallMethodNode(call)
+        } else {
+            Nodes::CallFuncNode(call)
+        }
+    }
+
+    // Method `method` of the type `name`, with the type arguments `args` filled in
+    fn find_method(
+        &self,
+        name: &str,
+        args: &[Either<VarType, ArrayVarType>],
+        method: &str,
+    ) -> Option<DefineFunctionNode<'a>> {
+        let ty: &TypeNode<'a> = self
+            .current_scope
+            .type_vec
+            .iter()
+            .find(|ty: &&TypeNode<'a>| ty.name == name)?;
+        let method: DefineFunctionNode<'a> = ty.method(method)?;
+
+        let method_args: Vec<ArgNode<'a>> = method
+            .args
+            .iter()
+            .map(|arg: &ArgNode<'a>| {
+                ArgNode::new(
+                    arg.name.clone(),
+                    substitute_ty(&arg.ty, &ty.ty_params, args),
+                    arg.default.clone(),
+                )
+            })
+            .collect();
+        let ret_ty: Option<Either<VarType, ArrayVarType>> = method
+            .ret_ty
+            .as_ref()
+            .map(|ret_ty| substitute_ty(ret_ty, &ty.ty_params, args));
+
+        Some(DefineFunctionNode {
+            args: method_args,
+            ret_ty,
+            ..method
+        })
+    }
+
+    fn parse_assign_to_var(&mut self, var_to_assign: Nodes<'a>) -> AssignToVarNode<'a> {
+        let var: CallVarNode<'a> = var_to_assign.get_call_var_node().unwrap();
+
+        if !var.0 .2 {
+            self.update_error_handler();
+            self.error_handler.throw_cant_mutate_var(&var.0 .0);
+        }
+
+        self.next(2);
+
+        let val: Box<Nodes<'a>> =
+            Box::new(self.parse_value_node(var.0 .0.clone(), var.0 .1 .1.clone()));
+
+        AssignToVarNode(var, val)
+    }
+
+    fn parse_assign_to_var_arr(&mut self, var_to_assign: Nodes<'a>) -> AssignToVarArrNode<'a> {
+        let var: CallVarArrNode<'a> = var_to_assign.get_call_var_arr_node().unwrap();
+
+        if !var.0 .0 .2 {
+            self.update_error_handler();
+            self.error_handler.throw_cant_mutate_var(&var.0 .0 .0);
+        }
+
+        self.next(2);
+
+        let var_ty: Either<VarType, ArrayVarType> = var
+            .0
+             .0
+             .1
+             .1
+            .clone()
+            .unwrap_right()
+            .index_ty(var.1.len())
+            .unwrap();
+
+        let val: Box<Nodes<'a>> = Box::new(self.parse_value_node(var.0 .0 .0.clone(), var_ty));
+
+        AssignToVarArrNode(var, val)
+    }
+
+    // * Functions
+
+    // TODO: Error handling
+    fn parse_function(&mut self) -> FunctionNode<'a> {
+        self.next(1);
+
+        let define_func_node: DefineFunctionNode<'a> = self.parse_define_function_node();
+
+        let mut new_node: FunctionNode<'a> =
+            FunctionNode::new(define_func_node.clone(), ScopeNode::new());
+
+        // Top-level functions were registered by `collect_declarations` already
+        let idx: usize = match self
+            .current_scope
+            .search_node(define_func_node.name.clone(), 1)
+            .0
+        {
+            Ok(idx) => {
+                self.current_scope.func_vec[idx] = new_node.clone();
+
+                idx
+            }
+            Err(_) => {
+                self.current_scope.func_vec.push(new_node.clone());
+
+                self.current_scope.func_vec.len() - 1
+            }
+        };
+
+        let scope: ScopeNode<'a> = self.parse_func_body(define_func_node);
+
+        new_node.scope = scope.clone();
+
+        self.current_scope.func_vec[idx].scope = scope;
+
+        new_node
+    }
+
+    // Methods belong to their type, so unlike functions they aren't added to the scope
+    fn parse_method(&mut self) -> FunctionNode<'a> {
+        self.next(1);
+
+        let define_func_node: DefineFunctionNode<'a> = self.parse_define_function_node();
+        let scope: ScopeNode<'a> = self.parse_func_body(define_func_node.clone());
+
+        FunctionNode::new(define_func_node, scope)
+    }
+
+    // `func(a: int): int { ... }`, starts on `func` and ends on the closing `}`
+    fn parse_lambda(&mut self) -> LambdaNode<'a> {
+        let token: Token<'a> = self.current_token;
+
+        let mut define_func_node: DefineFunctionNode<'a> = self.parse_define_function_node();
+        define_func_node.name = "lambda".to_owned();
+
+        // Function types have no variadic form or default values, so calls through the lambda
+        // couldn't use them
+        if define_func_node.variadic {
+            self.current_token = token;
+            self.update_error_handler();
+            self.error_handler.throw_variadic_lambda();
+        }
+
+        if define_func_node
+            .args
+            .iter()
+            .any(|arg: &ArgNode<'a>| arg.default.is_some())
+        {
+            self.current_token = token;
+            self.update_error_handler();
+            self.error_handler.throw_default_in_lambda();
+        }
+
+        self.captures
+            .push((self.current_scope.var_vec.len(), vec![]));
+
+        let scope: ScopeNode<'a> = self.parse_func_body(define_func_node.clone());
+
+        let (_, captures) = self.captures.pop().unwrap();
+
+        LambdaNode::new(FunctionNode::new(define_func_node, scope), captures, token)
+    }
+
+    // Starts on the first token of the body and ends on the closing `}`
+    fn parse_func_body(&mut self, define_func_node: DefineFunctionNode<'a>) -> ScopeNode<'a> {
+        let args: Vec<VarNode<'a>> = define_func_node
+            .args
+            .iter()
+            .map(|arg: &ArgNode<'a>| {
+                VarNode(
+                    arg.name.clone(),
+                    ValueNode(Left(Box::new(Nodes::NullNode)), arg.ty.clone()),
+                    false,
+                )
+            })
+            .collect();
+
+        // A function declared inside a loop can't leave it
+        let outer_ty_params: Vec<String> = self.ty_params.clone();
+        self.ty_params.extend(define_func_node.ty_params.clone());
+
+        let outer_func: Option<DefineFunctionNode<'a>> =
+            self.current_func.replace(define_func_node);
+        let outer_loop_labels: Vec<Option<String>> = std::mem::take(&mut self.loop_labels);
+        // Functions declared in a method aren't methods
+        let outer_self_ty: Option<VarType> = self.self_ty.take();
+
+        let scope: ScopeNode = self.parse_block(args);
+
+        self.current_func = outer_func;
+        self.loop_labels = outer_loop_labels;
+        self.self_ty = outer_self_ty;
+        self.ty_params = outer_ty_params;
+
+        scope
+    }
+
+    fn parse_define_function_node(&mut self) -> DefineFunctionNode<'a> {
+        let name: String = self.current_token.slice.to_owned();
+
+        self.update_error_handler();
+        let location: Location<'a> = self.error_handler.location();
+
+        // Methods belong to their type, they can share names with functions
+        if let (Ok(idx), None) = (
+            self.current_scope.search_node(name.clone(), 1).0,
+            &self.self_ty,
+        ) {
+            if !self.is_declared_here(&self.current_scope.func_vec[idx].define_node.location) {
+                self.error_handler.throw_name_already_used(1);
+            }
+        }
+
+        let ty_params: Vec<String> = self.parse_ty_params();
+
+        let outer_ty_params: Vec<String> = self.ty_params.clone();
+        self.ty_params.extend(ty_params.clone());
+
+        self.next(1);
+
+        let mut args: Vec<ArgNode<'a>> = vec![];
+        let mut args_name: Vec<String> = vec![];
+        let mut variadic: bool = false;
+
+        if self.peek().token_type == TokenType::CloseParen {
+            self.next(1);
+        }
+
+        while self.current_token.token_type != TokenType::CloseParen {
+            self.next(1);
+
+            let name_token: Token<'a> = self.current_token;
+
+            // `self` takes the type the method belongs to
+            if name_token.slice == "self" && self.peek().token_type != TokenType::Colon {
+                match (&self.self_ty, args.is_empty()) {
+                    (Some(self_ty), true) => {
+                        args.push(ArgNode::new("self".to_owned(), Left(self_ty.clone()), None));
+                        args_name.push("self".to_owned());
+                    }
+                    _ => {
+                        self.update_error_handler();
+                        self.error_handler.throw_misplaced_self();
+                    }
+                }
+
+                self.next(1);
+                continue;
+            }
+
+            let arg: ArgNode<'a>;
+            (arg, variadic) = self.parse_func_arg(&mut args_name);
+
+            // Otherwise positional calls couldn't leave the earlier ones out
+            if arg.default.is_none()
+                && !variadic
+                && args.iter().any(|arg: &ArgNode<'a>| arg.default.is_some())
+            {
+                self.current_token = name_token;
+                self.update_error_handler();
+                self.error_handler.throw_missing_default(&arg.name);
+            }
+
+            args.push(arg.clone());
+        }
+
+        self.next(1);
+        let ret_ty: Option<Either<VarType, ArrayVarType>> =
+            if self.current_token.token_type == TokenType::Colon {
+                self.next(1);
+                let tmp: Option<Either<VarType, ArrayVarType>> = Some(self.parse_ty());
+                self.next(1);
+
+                tmp
+            } else {
+                None
+            };
+        self.next(1);
+
+        self.ty_params = outer_ty_params;
+
+        DefineFunctionNode::new(name, ty_params, args, ret_ty, variadic, location)
+    }
+
+    // Starts on the first token after `{` and ends on the closing `}`. The block sees
+    // everything declared so far plus `vars`, but its own declarations are dropped once it ends
+    fn parse_block(&mut self, vars: Vec<VarNode<'a>>) -> ScopeNode<'a> {
+        // Only what the block adds is undone, cloning the whole scope would also clone the
+        // bodies of every function declared before it
+        let outer_stmts: Vec<Nodes<'a>> = std::mem::take(&mut self.current_scope.scope);
+        let var_len: usize = self.current_scope.var_vec.len();
+        let func_len: usize = self.current_scope.func_vec.len();
+        let type_len: usize = self.current_scope.type_vec.len();
+        let was_local: bool = self.error_handler.using_local_scope;
+
+        self.current_scope.var_vec.extend(vars);
+        self.error_handler.using_local_scope = true;
+
+        let scope: ScopeNode<'a> = ScopeNode {
+            scope: self.parse_scope(),
+            ..ScopeNode::new()
+        };
+
+        self.current_scope.scope = outer_stmts;
+        self.current_scope.var_vec.truncate(var_len);
+        self.current_scope.func_vec.truncate(func_len);
+        self.current_scope.type_vec.truncate(type_len);
+        self.error_handler.using_local_scope = was_local;
+
+        scope
+    }
+
+    fn parse_scope(&mut self) -> Vec<Nodes<'a>> {
+        while self.current_token.token_type != TokenType::CloseBrace {
+            let node: Nodes<'a> = self.parse_list(self.current_token);
+
+            self.current_scope.scope.push(node);
+
+            self.next(1);
+        }
+
+        std::mem::take(&mut self.current_scope.scope)
+    }
+
+    // Also returns whether the argument is variadic, `nums: int...`
+    fn parse_func_arg(&mut self, arg_vec: &mut Vec<String>) -> (ArgNode<'a>, bool) {
+        let name: String = self.current_token.slice.to_owned();
+
+        if !arg_vec
+            .clone()
+            .into_iter()
+            .any(|arg_name: String| arg_name == name)
+        {
+            arg_vec.push(name.clone());
+        } else {
+            self.update_error_handler();
+            self.error_handler.throw_arg_alreay_used(name.clone());
+        }
+
+        self.next(2);
+
+        let mut ty: Either<VarType, ArrayVarType> = self.parse_ty();
+
+        self.next(1);
+
+        if self.current_token.token_type == TokenType::Assign {
+            self.next(1);
+
+            let default: Nodes<'a> = self.parse_expr();
+
+            self.next(1);
+
+            return (ArgNode::new(name, ty, Some(default)), false);
+        }
+
+        if self.current_token.token_type != TokenType::Ellipsis {
+            return (ArgNode::new(name, ty, None), false);
+        }
+
+        if self.peek().token_type != TokenType::CloseParen {
+            self.update_error_handler();
+            self.error_handler.throw_variadic_not_last(&name);
+        }
+
+        ty = Right(ArrayVarType::from_elem_ty(ty, None));
+
+        self.next(1);
+
+        (ArgNode::new(name, ty, None), true)
+    }
+
+    fn get_current_func(&mut self) -> DefineFunctionNode<'a> {
+        match self.current_func.clone() {
+            Some(func) => func,
+            None => {
+                self.update_error_handler();
+                self.error_handler.throw_unkown_token();
+
+                unreachable!()
+            }
+        }
+    }
+
+    fn parse_return(&mut self) -> ReturnNode<'a> {
+        let func: DefineFunctionNode<'a> = self.get_current_func();
+
+        if self.peek().token_type == TokenType::Semicolon {
+            if let Some(ret_ty) = func.ret_ty {
+                self.update_error_handler();
+                self.error_handler
+                    .throw_missing_return_value(&func.name, ty_to_string(&Some(ret_ty)));
+            }
+
+            return ReturnNode::new(None);
+        }
+
+        if func.ret_ty.is_none() {
+            self.update_error_handler();
+            self.error_handler
+                .throw_used_return_when_no_return(func.name.clone());
+        }
+
+        self.next(1);
+
+        let ret_val: ValueNode<'a> = self.parse_value(func.name, func.ret_ty.unwrap());
+
+        ReturnNode::new(Some(ret_val))
+    }
+
+    fn parse_return_if(&mut self) -> ReturnIfNode<'a> {
+        let func: DefineFunctionNode<'a> = self.get_current_func();
+
+        self.next(1);
+
+        let cond: Nodes<'a> = self.parse_expr();
+
+        if self.peek().token_type != TokenType::Colon {
+            if let Some(ret_ty) = func.ret_ty {
+                self.next(1);
+                self.update_error_handler();
+                self.error_handler
+                    .throw_missing_return_value(&func.name, ty_to_string(&Some(ret_ty)));
+            }
+
+            return ReturnIfNode::new(Box::new(cond), None);
+        }
+
+        self.next(1);
+
+        if func.ret_ty.is_none() {
+            self.update_error_handler();
+            self.error_handler
+                .throw_used_return_when_no_return(func.name.clone());
+        }
+
+        self.next(1);
+
+        let ret_val: ValueNode<'a> = self.parse_value(func.name, func.ret_ty.unwrap());
+
+        ReturnIfNode::new(Box::new(cond), Some(ret_val))
+    }
+
+    // * Control flow
+
+    // `label: while cond {`, starts on the label and ends on the loop's last token
+    fn parse_labeled_loop(&mut self) -> Nodes<'a> {
+        let label: String = self.current_token.slice.to_owned();
+
+        if self.loop_labels.contains(&Some(label.clone())) {
+            self.update_error_handler();
+            self.error_handler.throw_label_already_used();
+        }
+
+        self.next(2);
+
+        match self.current_token.token_type {
+            TokenType::While => Nodes::WhileNode(self.parse_while(Some(label))),
+            TokenType::Do => Nodes::DoWhileNode(self.parse_do_while(Some(label))),
+            TokenType::For => Nodes::ForNode(self.parse_for(Some(label))),
+            TokenType::Loop => Nodes::LoopNode(self.parse_loop(Some(label))),
+            _ => {
+                self.update_error_handler();
+                self.error_handler.throw_label_not_on_loop();
+
+                unreachable!()
+            }
+        }
+    }
+
+    // The label after `break`, `continue` or `break_if`, if any, `break_if` separates it
+    // from the condition with `:`
+    fn parse_loop_jump(&mut self) -> Option<String> {
+        if self.loop_labels.is_empty() {
+            self.update_error_handler();
+            self.error_handler.throw_break_outside_loop();
+        }
+
+        let is_break_if: bool = self.current_token.token_type == TokenType::BreakIf;
+
+        if self.peek().token_type != TokenType::Identifier
+            || (is_break_if
+                && self
+                    .token_stream
+                    .get(self.tok_i + 1)
+                    .map(|token| token.token_type)
+                    != Some(TokenType::Colon))
+        {
+            return None;
+        }
+
+        self.next(1);
+
+        let label: String = self.current_token.slice.to_owned();
+
+        if !self.loop_labels.contains(&Some(label.clone())) {
+            self.update_error_handler();
+            self.error_handler.throw_unknown_label();
+        }
+
+        if is_break_if {
+            self.next(1);
+        }
+
+        Some(label)
+    }
+
+    fn parse_break_if(&mut self) -> BreakIfNode<'a> {
+        let label: Option<String> = self.parse_loop_jump();
+
+        self.next(1);
+
+        BreakIfNode::new(label, Box::new(self.parse_expr()))
+    }
+
+    // Loop bodies are parsed through this, so `break` and `continue` know which loops they're in
+    fn parse_loop_block(&mut self, label: Option<String>, vars: Vec<VarNode<'a>>) -> ScopeNode<'a> {
+        self.loop_labels.push(label);
+
+        let scope: ScopeNode<'a> = self.parse_block(vars);
+
+        self.loop_labels.pop();
+
+        scope
+    }
+
+    // Moves from the token before a block's `{` to the first token inside it
+    fn enter_block(&mut self) {
+        self.next(1);
+
+        if self.current_token.token_type != TokenType::OpenBrace {
+            self.update_error_handler();
+            self.error_handler.throw_unkown_token();
+        }
+
+        self.next(1);
+    }
+
+    fn parse_if(&mut self) -> IfNode<'a> {
+        self.next(1);
+
+        let cond: Nodes<'a> = self.parse_expr();
+
+        self.enter_block();
+
+        let scope: ScopeNode<'a> = self.parse_block(vec![]);
+
+        let else_scope: Option<ScopeNode<'a>> = if self.peek().token_type == TokenType::Else {
+            self.next(1);
+
+            if self.peek().token_type == TokenType::If {
+                self.next(1);
+
+                let mut else_scope: ScopeNode<'a> = ScopeNode::new();
+                else_scope.scope.push(Nodes::IfNode(self.parse_if()));
+
+                Some(else_scope)
+            } else {
+                self.enter_block();
+
+                Some(self.parse_block(vec![]))
+            }
+        } else {
+            None
+        };
+
+        IfNode::new(Box::new(cond), scope, else_scope)
+    }
+
+    fn parse_while(&mut self, label: Option<String>) -> WhileNode<'a> {
+        self.next(1);
+
+        let cond: Nodes<'a> = self.parse_expr();
+
+        self.enter_block();
+
+        WhileNode::new(
+            label.clone(),
+            Box::new(cond),
+            self.parse_loop_block(label, vec![]),
+        )
+    }
+
+    // `do { ... } while cond`, variables declared in the body aren't visible in the condition
+    fn parse_do_while(&mut self, label: Option<String>) -> DoWhileNode<'a> {
+        self.enter_block();
+
+        let scope: ScopeNode<'a> = self.parse_loop_block(label.clone(), vec![]);
+
+        self.next(1);
+
+        if self.current_token.token_type != TokenType::While {
+            self.update_error_handler();
+            self.error_handler.throw_unkown_token();
+        }
+
+        self.next(1);
+
+        DoWhileNode::new(label, scope, Box::new(self.parse_expr()))
+    }
+
+    // `for x in iterable | step {`, `for i, x in arr {`
+    fn parse_for(&mut self, label: Option<String>) -> ForNode<'a> {
+        self.next(1);
+
+        let item_token: Token<'a> = self.current_token;
+        let mut item: String = item_token.slice.to_owned();
+        let mut index: Option<String> = None;
+
+        if self.peek().token_type == TokenType::Comma {
+            self.next(2);
+
+            index = Some(item);
+            item = self.current_token.slice.to_owned();
+
+            if item != "_" && index.as_ref() == Some(&item) {
                 self.update_error_handler();
-                self.error_handler
-                    .throw_cant_use_val_in_arr_call(val_ty.to_string());
+                self.error_handler.throw_name_already_used(0);
+            }
+        }
+
+        self.next(1);
+
+        if self.current_token.token_type != TokenType::In {
+            self.update_error_handler();
+            self.error_handler.throw_unkown_token();
+        }
+
+        self.next(1);
+        self.step_follows = true;
+
+        let iterable: Nodes<'a> = self.parse_expr();
+
+        let step: Option<Box<Nodes<'a>>> = if self.peek().token_type == TokenType::Next {
+            self.next(2);
+
+            Some(Box::new(self.parse_expr()))
+        } else {
+            None
+        };
+
+        let int_ty: Either<VarType, ArrayVarType> = Left(VarType::Int { ty: IntType::I64 });
+
+        // Arrays are indexed by position and maps by key, a map alone gives its keys
+        let (index_ty, item_ty) = match iterable.get_ty() {
+            Some(Left(VarType::Range { ty })) => {
+                if index.is_some() {
+                    self.current_token = item_token;
+                    self.update_error_handler();
+                    self.error_handler.throw_range_index();
+                }
+
+                (int_ty, Left(VarType::Int { ty }))
+            }
+            Some(Right(arr_ty)) => (int_ty, arr_ty.elem_ty()),
+            Some(Left(VarType::Map { key, val })) if index.is_some() => (*key, *val),
+            Some(Left(VarType::Map { key, val: _ })) => (*key.clone(), *key),
+            // The checker reports ranges with ends that aren't integers
+            None if Self::is_range(&iterable) => (int_ty.clone(), int_ty),
+            ty => {
+                self.current_token = iterable.get_token().unwrap_or(self.current_token);
+                self.update_error_handler();
+                self.error_handler.throw_not_iterable(ty_to_string(&ty));
+
+                unreachable!()
+            }
+        };
+
+        let vars: Vec<VarNode<'a>> = [
+            index.clone().map(|index| (index, index_ty)),
+            Some((item.clone(), item_ty)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|(name, _)| name != "_")
+        .map(|(name, ty)| VarNode(name, ValueNode(Left(Box::new(Nodes::NullNode)), ty), false))
+        .collect();
+
+        self.enter_block();
+
+        let scope: ScopeNode<'a> = self.parse_loop_block(label.clone(), vars);
+
+        ForNode::new(label, item, index, Box::new(iterable), step, scope)
+    }
+
+    fn is_range(node: &Nodes<'a>) -> bool {
+        matches!(
+            node,
+            Nodes::ProcessedMathNode(math) if matches!(
+                math.out_stream.last(),
+                Some(Nodes::MathOpTypeNode(op))
+                    if matches!(op.op, OpType::InclusiveRange | OpType::ExclusiveRange)
+            )
+        )
+    }
+
+    fn parse_loop(&mut self, label: Option<String>) -> LoopNode<'a> {
+        self.enter_block();
+
+        LoopNode::new(label.clone(), self.parse_loop_block(label, vec![]))
+    }
+
+    // * Types
+
+    // Methods are skipped when `with_methods` is false, while collecting declarations
+    fn parse_type(&mut self, with_methods: bool) -> TypeNode<'a> {
+        self.next(1);
+
+        let name: String = self.current_token.slice.to_owned();
+
+        self.update_error_handler();
+
+        let location: Location<'a> = self.error_handler.location();
+        let ty_params: Vec<String> = self.parse_ty_params();
+        let interfaces: Vec<Token<'a>> = self.parse_impl_list();
+
+        let mut node: TypeNode = TypeNode::new(name, ty_params.clone(), vec![], location);
+        node.interfaces = interfaces
+            .iter()
+            .map(|token: &Token<'a>| token.slice.to_owned())
+            .collect();
+
+        // Signatures of the methods registered by `collect_declarations` stay in the scope, so
+        // methods can call the ones declared after them
+        let collected: Vec<TypeArgNode<'a>> =
+            match self.current_scope.search_node(node.name.clone(), 2).0 {
+                Ok(idx)
+                    if with_methods
+                        && self.current_scope.type_vec[idx].location == node.location =>
+                {
+                    self.current_scope.type_vec[idx]
+                        .args
+                        .iter()
+                        .filter(|arg: &&TypeArgNode<'a>| arg.val.is_right())
+                        .cloned()
+                        .collect()
+                }
+                _ => vec![],
+            };
+
+        let idx: usize = self.declare_type(node.clone());
+
+        self.current_scope.type_vec[idx].args = collected;
+
+        let mut args_vec: Vec<TypeArgNode<'a>> = vec![];
+        let mut args_vec_names: Vec<String> = vec![];
+
+        let outer_ty_params: Vec<String> = self.ty_params.clone();
+        self.ty_params.extend(ty_params.clone());
+
+        let outer_self_ty: Option<VarType> = self.self_ty.replace(VarType::Type {
+            name: node.name.clone(),
+            args: ty_params
+                .into_iter()
+                .map(|name: String| Left(VarType::Generic { name }))
+                .collect(),
+        });
+
+        self.next(1);
+
+        loop {
+            self.next(1);
+
+            if self.current_token.token_type == TokenType::CloseBrace {
+                break;
+            }
+
+            // Only the signature, the body is parsed with the rest of the file
+            if !with_methods && self.current_token.token_type == TokenType::Func {
+                self.next(1);
+
+                let define_node: DefineFunctionNode<'a> = self.parse_define_function_node();
+
+                self.seek(self.tok_i - 2);
+                self.skip_block();
+
+                self.current_scope.type_vec[idx].args.push(TypeArgNode::new(
+                    define_node.name.clone(),
+                    Right(FunctionNode::new(define_node, ScopeNode::new())),
+                    false,
+                ));
+
+                continue;
+            }
+
+            let arg: TypeArgNode<'a> = self.parse_type_arg(&mut args_vec_names);
+
+            args_vec.push(arg.clone());
+
+            let type_args: &mut Vec<TypeArgNode<'a>> = &mut self.current_scope.type_vec[idx].args;
+
+            match type_args
+                .iter()
+                .position(|found: &TypeArgNode<'a>| found.name == arg.name)
+            {
+                Some(pos) => type_args[pos] = arg,
+                None => type_args.push(arg),
+            }
+        }
+
+        self.ty_params = outer_ty_params;
+        self.self_ty = outer_self_ty;
+
+        node.args = args_vec;
+
+        if with_methods {
+            self.current_scope.type_vec[idx].args = node.args.clone();
+            self.check_impls(&node, &interfaces);
+
+            if !node.interfaces.is_empty() {
+                self.impls
+                    .insert(node.name.clone(), node.interfaces.clone());
+            }
+        }
+
+        node
+    }
+
+    // `: Shape, Named` after the name of a type, the interfaces it implements
+    fn parse_impl_list(&mut self) -> Vec<Token<'a>> {
+        let mut interfaces: Vec<Token<'a>> = vec![];
+
+        if self.peek().token_type != TokenType::Colon {
+            return interfaces;
+        }
+
+        self.next(1);
+
+        loop {
+            self.next(1);
+
+            let name: String = self.current_token.slice.to_owned();
+            let idx: usize = self.search_node(name.clone(), true, 2).0.unwrap();
+
+            if self.current_scope.type_vec[idx].methods.is_none() {
+                self.update_error_handler();
+                self.error_handler.throw_not_an_interface(&name);
             }
 
-            unpacked_node.slice.parse().unwrap()
-        } else {
-            if self.current_token.token_type != TokenType::Number {
-                let val_ty: VarType = self.get_ty_from_val(self.current_token).unwrap();
+            if interfaces
+                .iter()
+                .any(|interface: &Token<'a>| interface.slice == name)
+            {
+                self.update_error_handler();
+                self.error_handler.throw_interface_listed_twice(&name);
+            }
+
+            interfaces.push(self.current_token);
+
+            if self.peek().token_type != TokenType::Comma {
+                break;
+            }
+
+            self.next(1);
+        }
+
+        interfaces
+    }
+
+    // Every method of the interfaces `node` implements has to be in it with the same signature
+    fn check_impls(&mut self, node: &TypeNode<'a>, interfaces: &[Token<'a>]) {
+        for interface in interfaces {
+            let idx: usize = self
+                .current_scope
+                .search_node(interface.slice.to_owned(), 2)
+                .0
+                .unwrap();
+            let methods: Vec<DefineFunctionNode<'a>> = self.current_scope.type_vec[idx]
+                .methods
+                .clone()
+                .unwrap_or_default();
+
+            for method in &methods {
+                let Some(found) = node.method(&method.name) else {
+                    self.current_token = *interface;
+                    self.update_error_handler();
+                    self.error_handler.throw_missing_method(
+                        &node.name,
+                        &method.name,
+                        interface.slice,
+                    );
+
+                    unreachable!()
+                };
+
+                let (expected, found_sig): (String, String) =
+                    (Self::method_sig(method), Self::method_sig(&found));
+
+                if expected != found_sig {
+                    self.current_token = found.location.token;
+                    self.update_error_handler();
+                    self.error_handler.throw_wrong_method_signature(
+                        &method.name,
+                        interface.slice,
+                        expected,
+                        found_sig,
+                    );
+                }
+            }
+        }
+    }
+
+    // `(self, f64): f64`, `self` stands for the type the method belongs to
+    fn method_sig(method: &DefineFunctionNode<'a>) -> String {
+        let args: Vec<String> = method
+            .args
+            .iter()
+            .enumerate()
+            .map(
+                |(i, arg): (usize, &ArgNode<'a>)| match (i, arg.name.as_str()) {
+                    (0, "self") => "self".to_owned(),
+                    _ => ty_to_string(&Some(arg.ty.clone())),
+                },
+            )
+            .collect();
+
+        match &method.ret_ty {
+            Some(ret_ty) => format!(
+                "({}): {}",
+                args.join(", "),
+                ty_to_string(&Some(ret_ty.clone()))
+            ),
+            None => format!("({})", args.join(", ")),
+        }
+    }
+
+    // `interface Shape { func area(self): f64; }`, only the signatures of the methods
+    fn parse_interface(&mut self) -> TypeNode<'a> {
+        self.next(1);
+
+        let name: String = self.current_token.slice.to_owned();
+
+        self.update_error_handler();
+
+        let location: Location<'a> = self.error_handler.location();
+        let idx: usize = self.declare_type(TypeNode::new_interface(
+            name.clone(),
+            vec![],
+            location.clone(),
+        ));
+        let outer_self_ty: Option<VarType> = self.self_ty.replace(VarType::Type {
+            name: name.clone(),
+            args: vec![],
+        });
+
+        let mut methods: Vec<DefineFunctionNode<'a>> = vec![];
+
+        self.next(1);
+
+        loop {
+            self.next(1);
+
+            if self.current_token.token_type == TokenType::CloseBrace {
+                break;
+            }
+
+            if self.current_token.token_type != TokenType::Func {
+                self.update_error_handler();
+                self.error_handler.throw_unkown_token();
+            }
+
+            self.next(1);
+
+            if methods
+                .iter()
+                .any(|method: &DefineFunctionNode<'a>| method.name == self.current_token.slice)
+            {
+                self.update_error_handler();
+                self.error_handler.throw_name_already_used(3);
+            }
+
+            let method: DefineFunctionNode<'a> = self.parse_define_function_node();
+
+            self.seek(self.tok_i - 2);
+
+            if self.current_token.token_type != TokenType::Semicolon {
+                self.update_error_handler();
+                self.error_handler.throw_interface_method_body();
+            }
+
+            methods.push(method);
+        }
+
+        self.self_ty = outer_self_ty;
+
+        let node: TypeNode<'a> = TypeNode::new_interface(name, methods, location);
+
+        self.current_scope.type_vec[idx] = node.clone();
+
+        node
+    }
+
+    // Adds the type or enum to the scope and returns its index, the entry registered by
+    // `collect_declarations` for the same declaration is replaced
+    fn declare_type(&mut self, node: TypeNode<'a>) -> usize {
+        match self.current_scope.search_node(node.name.clone(), 2).0 {
+            Ok(idx) if self.current_scope.type_vec[idx].location == node.location => {
+                self.current_scope.type_vec[idx] = node;
+
+                idx
+            }
+            Ok(_) => {
+                self.error_handler.throw_name_already_used(2);
+
+                unreachable!()
+            }
+            Err(_) => {
+                self.current_scope.type_vec.push(node);
+
+                self.current_scope.type_vec.len() - 1
+            }
+        }
+    }
+
+    fn parse_type_arg(&mut self, arg_vec: &mut Vec<String>) -> TypeArgNode<'a> {
+        let visibility: bool = self.current_token.token_type == TokenType::Priv;
+
+        if self.current_token.token_type == TokenType::Func {
+            self.next(1);
+
+            let name: String = self.current_token.slice.to_owned();
+
+            if !arg_vec
+                .clone()
+                .into_iter()
+                .any(|arg_name: String| arg_name == name)
+            {
+                arg_vec.push(name.clone());
+            } else {
+                self.update_error_handler();
+                self.error_handler.throw_arg_alreay_used(name.clone());
+            }
+
+            self.back();
+
+            let val: Either<Either<VarType, ArrayVarType>, FunctionNode<'a>> =
+                Right(self.parse_method());
+
+            TypeArgNode::new(name, val, visibility)
+        } else {
+            let name: String = self.current_token.slice.to_owned();
+
+            if !arg_vec
+                .clone()
+                .into_iter()
+                .any(|arg_name: String| arg_name == name)
+            {
+                arg_vec.push(name.clone());
+            } else {
+                self.update_error_handler();
+                self.error_handler.throw_arg_alreay_used(name.clone());
+            }
+
+            self.next(2);
+
+            let val: Either<Either<VarType, ArrayVarType>, FunctionNode<'a>> =
+                Left(self.parse_ty());
+
+            self.next(1);
+
+            TypeArgNode::new(name, val, visibility)
+        }
+    }
+
+    fn parse_type_init(&mut self) -> InitTypeNode<'a> {
+        let type_token: Token<'a> = self.current_token;
+        let idx: (Result<usize, usize>, bool) =
+            self.search_node(self.current_token.slice.to_owned(), true, 2);
 
-                self.update_error_handler();
-                self.error_handler
-                    .throw_cant_use_val_in_arr_call(val_ty.to_string());
-            }
+        let found_node: TypeNode = self.current_scope.type_vec[idx.0.unwrap()].clone();
 
-            self.current_token.slice.parse().unwrap()
+        if found_node.variants.is_some() {
+            self.update_error_handler();
+            self.error_handler.throw_enum_type_init(&found_node.name);
         }
-    }
 
-    fn parse_call_var_arr(&mut self) -> CallVarArrNode<'a> {
-        let var_to_call: CallVarNode<'a> = self.parse_call_var();
+        if found_node.methods.is_some() {
+            self.update_error_handler();
+            self.error_handler
+                .throw_interface_type_init(&found_node.name);
+        }
 
         self.next(2);
 
-        let index_to_call: isize = self.parse_index();
+        // Methods are in the args too, the values only go to fields
+        let field_args: Vec<TypeArgNode<'a>> = found_node
+            .args
+            .iter()
+            .filter(|arg: &&TypeArgNode<'a>| arg.val.is_left())
+            .cloned()
+            .collect();
+        let mut fields: Vec<(String, ValueNode<'a>)> = vec![];
+        let mut i: usize = 0;
 
-        self.next(1);
+        while self.current_token.token_type != TokenType::CloseBrace {
+            let field_name: String = field_args[i].name.clone();
 
-        while self.current_token.token_type == TokenType::CloseBracket {
-            self.next(1);
-        }
+            fields.push((
+                field_name.clone(),
+                self.parse_value(field_name, field_args[i].val.clone().unwrap_left()),
+            ));
 
-        self.back();
+            self.next(if self.peek().token_type == TokenType::Comma {
+                2
+            } else {
+                1
+            });
 
-        if index_to_call < 0
-            || var_to_call.0.clone().1 .0.unwrap_right().last().unwrap().1 < index_to_call
-        {
-            self.update_error_handler();
-            self.error_handler
-                .throw_cant_use_num_array(var_to_call.0 .0.as_str(), index_to_call);
+            i += 1;
         }
 
-        CallVarArrNode(var_to_call, index_to_call)
-    }
+        if found_node.ty_params.is_empty() {
+            return InitTypeNode::new(found_node.name, vec![], fields, type_token);
+        }
 
-    fn parse_assign_to_var(&mut self, var_to_assign: Nodes<'a>) -> AssignToVarNode<'a> {
-        self.next(2);
+        let mut ty_args: Vec<Option<Either<VarType, ArrayVarType>>> =
+            vec![None; found_node.ty_params.len()];
+
+        for (value, field) in fields.iter().zip(&field_args) {
+            let val_ty: Option<Either<VarType, ArrayVarType>> = match &value.1 .0 {
+                Left(node) => node.get_ty(),
+                Right(elems) => elems
+                    .first()
+                    .and_then(|elem| elem.0.get_ty())
+                    .map(|elem_ty| {
+                        Right(ArrayVarType::from_elem_ty(
+                            elem_ty,
+                            Some(elems.len() as isize),
+                        ))
+                    }),
+            };
 
-        let var: CallVarNode<'a> = var_to_assign.get_call_var_node().unwrap();
+            if let (Some(val_ty), Left(field_ty)) = (val_ty, &field.val) {
+                infer_ty_args(field_ty, &val_ty, &found_node.ty_params, &mut ty_args);
+            }
+        }
 
-        let var_ty: VarType = var.0 .1 .1.clone().unwrap_left();
-        let val_ty: VarType = self.get_ty_from_val(self.current_token).unwrap();
+        let ty_args: Vec<Either<VarType, ArrayVarType>> =
+            self.resolve_ty_args(&found_node.name, &found_node.ty_params, ty_args, type_token);
 
-        if var_ty != val_ty {
-            self.update_error_handler();
-            self.error_handler.throw_wrong_assign_type(
-                &var.0 .0,
-                val_ty.to_string(),
-                var_ty.to_string(),
-            );
+        // The fields are checked against their types with the inferred arguments
+        for (_, value) in fields.iter_mut() {
+            value.1 = substitute_ty(&value.1, &found_node.ty_params, &ty_args);
         }
-        let val: Box<Nodes<'a>> = Box::new(self.parse_list(self.current_token));
 
-        AssignToVarNode(var, val)
+        InitTypeNode::new(found_node.name, ty_args, fields, type_token)
     }
 
-    fn parse_assign_to_var_arr(&mut self, var_to_assign: Nodes<'a>) -> AssignToVarArrNode<'a> {
-        self.back();
-        self.back();
+    // * Enums
 
-        let var: CallVarArrNode<'a> = var_to_assign.get_call_var_arr_node().unwrap();
-        let index: isize = self.current_token.slice.parse().unwrap();
+    fn parse_enum(&mut self) -> TypeNode<'a> {
+        self.next(1);
+
+        let name: String = self.current_token.slice.to_owned();
+
+        self.update_error_handler();
+
+        let location: Location<'a> = self.error_handler.location();
+        let idx: usize = self.declare_type(TypeNode::new_enum(name.clone(), vec![], location));
 
-        self.next(4);
+        let mut variants: Vec<VariantNode<'a>> = vec![];
 
-        let var_ty: VarType = var.0 .0 .1 .1.clone().unwrap_right().to_var_type();
-        let val_ty: VarType = self.get_ty_from_val(self.current_token).unwrap();
+        self.next(1);
+
+        loop {
+            self.next(1);
+
+            match self.current_token.token_type {
+                TokenType::CloseBrace => break,
+                TokenType::Comma => continue,
+                _ => {}
+            }
 
-        if var_ty != val_ty {
             self.update_error_handler();
-            self.error_handler.throw_wrong_assign_type(
-                &var.0 .0 .0,
-                val_ty.to_string(),
-                var_ty.to_string(),
-            );
+
+            if variants
+                .iter()
+                .any(|variant: &VariantNode<'a>| variant.name == self.current_token.slice)
+            {
+                self.error_handler.throw_variant_already_used();
+            }
+
+            let variant_name: String = self.current_token.slice.to_owned();
+            let location: Location<'a> = self.error_handler.location();
+            let mut payload: Vec<Either<VarType, ArrayVarType>> = vec![];
+
+            if self.peek().token_type == TokenType::OpenParen {
+                self.next(2);
+
+                while self.current_token.token_type != TokenType::CloseParen {
+                    payload.push(self.parse_ty());
+
+                    self.next(if self.peek().token_type == TokenType::Comma {
+                        2
+                    } else {
+                        1
+                    });
+                }
+            }
+
+            variants.push(VariantNode::new(variant_name, payload, location));
         }
 
-        let val: Box<Nodes<'a>> = Box::new(self.parse_list(self.current_token));
+        self.current_scope.type_vec[idx].variants = Some(variants);
 
-        AssignToVarArrNode(var, index, val)
+        self.current_scope.type_vec[idx].clone()
     }
 
-    // * Functions
+    // The variants of the enum named `name`, `None` when it's a type
+    fn get_variants(&mut self, name: &str) -> Option<Vec<VariantNode<'a>>> {
+        let idx: usize = self.search_node(name.to_owned(), true, 2).0.unwrap();
 
-    // TODO: Error handling
-    fn parse_function(&mut self) -> FunctionNode<'a> {
-        self.next(1);
+        self.current_scope.type_vec[idx].variants.clone()
+    }
 
-        let define_func_node: DefineFunctionNode = self.parse_define_function_node();
+    fn parse_enum_init(&mut self) -> InitEnumNode<'a> {
+        let enum_token: Token<'a> = self.current_token;
+        let name: String = enum_token.slice.to_owned();
 
-        let mut new_node: FunctionNode<'a> =
-            FunctionNode::new(define_func_node.clone(), ScopeNode::new());
+        let variants: Vec<VariantNode<'a>> = match self.get_variants(&name) {
+            Some(variants) => variants,
+            None => {
+                self.update_error_handler();
+                self.error_handler.throw_not_an_enum(&name);
 
-        self.current_scope.func_vec.push(new_node.clone());
+                unreachable!()
+            }
+        };
 
-        let scope: ScopeNode = self.parse_scope(define_func_node);
+        self.next(2);
 
-        new_node.scope = scope.clone();
+        let variant: VariantNode<'a> = self.find_variant(&name, &variants);
 
-        self.current_scope.func_vec.last_mut().unwrap().scope = scope;
+        let mut args: Vec<Nodes<'a>> = vec![];
 
-        new_node
+        if self.peek().token_type == TokenType::OpenParen {
+            self.next(2);
+
+            while self.current_token.token_type != TokenType::CloseParen {
+                args.push(self.parse_list(self.current_token));
+
+                self.next(if self.peek().token_type == TokenType::Comma {
+                    2
+                } else {
+                    1
+                });
+            }
+        }
+
+        InitEnumNode::new(name, variant, args, enum_token)
     }
 
-    fn parse_define_function_node(&mut self) -> DefineFunctionNode {
-        let name: String = self.current_token.slice.to_owned();
+    // Looks up the variant named by the current token
+    fn find_variant(&mut self, name: &str, variants: &[VariantNode<'a>]) -> VariantNode<'a> {
+        match variants
+            .iter()
+            .find(|variant: &&VariantNode<'a>| variant.name == self.current_token.slice)
+        {
+            Some(variant) => variant.clone(),
+            None => {
+                self.update_error_handler();
+                self.error_handler.throw_no_variant(name);
 
-        self.search_node(name.clone(), false, 1).0.is_ok().then(|| {
-            self.update_error_handler();
-            self.error_handler.throw_name_already_used(1);
-        });
+                unreachable!()
+            }
+        }
+    }
 
-        self.next(1);
+    fn parse_switch(&mut self) -> SwitchNode<'a> {
+        let switch_token: Token<'a> = self.current_token;
 
-        let mut args: Vec<ArgNode> = vec![];
-        let mut args_name: Vec<String> = vec![];
+        self.next(1);
 
-        while self.current_token.token_type != TokenType::CloseParen {
-            self.next(1);
+        let val_token: Token<'a> = self.current_token;
+        let val: Nodes<'a> = self.parse_expr();
 
-            let arg: ArgNode = self.parse_func_arg(&mut args_name);
+        let (name, variants): (String, Vec<VariantNode<'a>>) = match val.get_ty() {
+            Some(Left(VarType::Type { name, args: _ })) if self.get_variants(&name).is_some() => {
+                let variants: Vec<VariantNode<'a>> = self.get_variants(&name).unwrap();
 
-            args.push(arg.clone());
-        }
+                (name, variants)
+            }
+            ty => {
+                self.current_token = val_token;
+                self.update_error_handler();
+                self.error_handler
+                    .throw_switch_on_non_enum(ty_to_string(&ty));
 
-        self.next(1);
-        let ret_ty: Option<Either<VarType, ArrayVarType>> =
-            if self.current_token.token_type == TokenType::Colon {
-                self.next(1);
-                let tmp: Option<Either<VarType, ArrayVarType>> = Some(self.parse_ty());
-                self.next(1);
+                unreachable!()
+            }
+        };
 
-                tmp
-            } else {
-                None
-            };
-        self.next(1);
+        self.enter_block();
 
-        DefineFunctionNode::new(name, args, ret_ty)
-    }
+        let mut cases: Vec<CaseNode<'a>> = vec![];
+        let mut default: Option<ScopeNode<'a>> = None;
 
-    fn parse_scope(&mut self, func: DefineFunctionNode) -> ScopeNode<'a> {
         while self.current_token.token_type != TokenType::CloseBrace {
-            if self.current_token.token_type == TokenType::Return {
-                if func.clone().ret_ty.is_some() {
-                    let return_node: Nodes =
-                        Nodes::ReturnNode(self.parse_return(func.ret_ty.clone()));
+            match self.current_token.token_type {
+                TokenType::Case => {
+                    let case: CaseNode<'a> = self.parse_case(&name, &variants, &cases);
 
-                    self.current_scope.scope.push(return_node);
-                } else {
+                    cases.push(case);
+                }
+                TokenType::Default if default.is_none() => {
+                    self.enter_block();
+
+                    default = Some(self.parse_block(vec![]));
+                }
+                _ => {
                     self.update_error_handler();
-                    self.error_handler
-                        .throw_used_return_when_no_return(func.name.clone())
+                    self.error_handler.throw_unkown_token();
                 }
             }
 
-            let node: Nodes<'a> = self.parse_list(self.current_token);
-
-            self.current_scope.scope.push(node);
             self.next(1);
         }
 
-        self.current_scope.clone()
-    }
+        let missing: Vec<String> = variants
+            .into_iter()
+            .map(|variant: VariantNode<'a>| variant.name)
+            .filter(|variant: &String| !cases.iter().any(|case| &case.variant == variant))
+            .collect();
 
-    fn parse_func_arg(&mut self, arg_vec: &mut Vec<String>) -> ArgNode {
-        let name: String = self.current_token.slice.to_owned();
+        if default.is_none() && !missing.is_empty() {
+            let last_token: Token<'a> = self.current_token;
 
-        if !arg_vec
-            .clone()
-            .into_iter()
-            .any(|arg_name: String| arg_name == name)
-        {
-            arg_vec.push(name.clone());
-        } else {
+            self.current_token = switch_token;
             self.update_error_handler();
-            self.error_handler.throw_arg_alreay_used(name.clone());
-        }
+            self.error_handler.throw_missing_variants(&name, missing);
 
-        self.next(2);
+            self.current_token = last_token;
+        }
 
-        let ty: Either<VarType, ArrayVarType> = self.parse_ty();
+        SwitchNode::new(Box::new(val), cases, default)
+    }
 
+    // `case Variant(a, b) { ... }`, the bindings are immutable variables of the case's block
+    fn parse_case(
+        &mut self,
+        name: &str,
+        variants: &[VariantNode<'a>],
+        cases: &[CaseNode<'a>],
+    ) -> CaseNode<'a> {
         self.next(1);
 
-        ArgNode::new(name, ty)
-    }
+        let variant: VariantNode<'a> = self.find_variant(name, variants);
 
-    fn parse_return(&mut self, ret_ty: Option<Either<VarType, ArrayVarType>>) -> ReturnNode<'a> {
-        self.next(1);
+        if cases
+            .iter()
+            .any(|case: &CaseNode<'a>| case.variant == variant.name)
+        {
+            self.update_error_handler();
+            self.error_handler.throw_variant_already_matched();
+        }
 
-        let ret_val: ValueNode<'a> = self.parse_value("".to_owned(), ret_ty.unwrap());
+        let variant_token: Token<'a> = self.current_token;
+        let mut bindings: Vec<String> = vec![];
 
-        ReturnNode::new(ret_val)
-    }
+        if self.peek().token_type == TokenType::OpenParen {
+            self.next(2);
 
-    // * Types
+            while self.current_token.token_type != TokenType::CloseParen {
+                let binding: String = self.current_token.slice.to_owned();
 
-    fn parse_type(&mut self) -> TypeNode<'a> {
-        self.next(1);
+                if binding != "_" && bindings.contains(&binding) {
+                    self.update_error_handler();
+                    self.error_handler.throw_name_already_used(0);
+                }
 
-        let name: String = self.current_token.slice.to_owned();
+                bindings.push(binding);
 
-        self.search_node(name.clone(), false, 2).0.is_ok().then(|| {
-            self.update_error_handler();
-            self.error_handler.throw_name_already_used(2);
-        });
+                self.next(if self.peek().token_type == TokenType::Comma {
+                    2
+                } else {
+                    1
+                });
+            }
+        }
 
-        let mut args_vec: Vec<TypeArgNode<'a>> = vec![];
-        let mut args_vec_names: Vec<String> = vec![];
+        if bindings.len() != variant.payload.len() {
+            self.current_token = variant_token;
+            self.update_error_handler();
+            self.error_handler.throw_wrong_payload_count(
+                &variant.name,
+                variant.payload.len(),
+                bindings.len(),
+                &variant.location,
+            );
+        }
 
-        let mut node: TypeNode = TypeNode::new(name, vec![]);
+        let vars: Vec<VarNode<'a>> = bindings
+            .iter()
+            .zip(&variant.payload)
+            .filter(|(binding, _)| *binding != "_")
+            .map(|(binding, ty)| {
+                VarNode(
+                    binding.clone(),
+                    ValueNode(Left(Box::new(Nodes::NullNode)), ty.clone()),
+                    false,
+                )
+            })
+            .collect();
+
+        self.enter_block();
+
+        CaseNode::new(variant.name, bindings, self.parse_block(vars))
+    }
 
-        self.current_scope.type_vec.push(node.clone());
+    // * Modules
 
-        self.next(1);
+    fn parse_item_names(&mut self) -> Vec<Token<'a>> {
+        let mut names: Vec<Token<'a>> = vec![];
 
-        loop {
+        if self.current_token.token_type == TokenType::OpenBrace {
             self.next(1);
 
-            if self.current_token.token_type == TokenType::CloseBrace {
-                break;
-            }
-
-            let arg: TypeArgNode<'a> = self.parse_type_arg(&mut args_vec_names);
+            while self.current_token.token_type != TokenType::CloseBrace {
+                names.push(self.current_token);
 
-            args_vec.push(arg.clone());
-            self.current_scope
-                .type_vec
-                .last_mut()
-                .unwrap()
-                .args
-                .push(arg);
+                self.next(if self.peek().token_type == TokenType::Comma {
+                    2
+                } else {
+                    1
+                });
+            }
+        } else {
+            names.push(self.current_token);
         }
 
+        names
+    }
+
+    fn parse_import(&mut self) -> ImportNode {
         self.next(1);
 
-        node.args = args_vec;
+        let names: Vec<Token<'a>> = self.parse_item_names();
 
-        node
-    }
+        self.next(2);
 
-    fn parse_type_arg(&mut self, arg_vec: &mut Vec<String>) -> TypeArgNode<'a> {
-        let visibility: bool = self.current_token.token_type == TokenType::Priv;
+        let mut module: String = self.current_token.slice.to_owned();
 
-        if self.current_token.token_type == TokenType::Func {
-            self.next(1);
+        while self.peek().token_type == TokenType::DoubleColon {
+            self.next(2);
 
-            let name: String = self.current_token.slice.to_owned();
+            module = format!("{}::{}", module, self.current_token.slice);
+        }
 
-            if !arg_vec
-                .clone()
-                .into_iter()
-                .any(|arg_name: String| arg_name == name)
-            {
-                arg_vec.push(name.clone());
-            } else {
+        let exports: ScopeNode<'a> = match self.modules.get(&module) {
+            Some(exports) => exports.clone(),
+            None => {
                 self.update_error_handler();
-                self.error_handler.throw_arg_alreay_used(name.clone());
+                self.error_handler.throw_module_not_found(&module);
+
+                ScopeNode::new()
             }
+        };
 
-            self.back();
+        for name in &names {
+            let vec_to_search: Option<u8> = (0..3).find(|vec_to_search: &u8| {
+                !exports.search_node(name.slice.into(), *vec_to_search).1
+            });
 
-            let val: Either<Either<VarType, ArrayVarType>, FunctionNode<'a>> =
-                Right(self.parse_function());
+            if vec_to_search.is_none() {
+                self.current_token = *name;
+                self.update_error_handler();
+                self.error_handler.throw_name_not_exported(&module);
+            }
 
-            TypeArgNode::new(name, val, visibility)
-        } else {
-            let name: String = self.current_token.slice.to_owned();
+            let vec_to_search: u8 = vec_to_search.unwrap();
 
-            if !arg_vec
-                .clone()
-                .into_iter()
-                .any(|arg_name: String| arg_name == name)
+            let idx: usize = exports
+                .search_node(name.slice.into(), vec_to_search)
+                .0
+                .unwrap();
+
+            // Imports run once while collecting declarations and again when parsing, seeing
+            // the very same item again isn't a conflict
+            if let Ok(found_idx) = self
+                .current_scope
+                .search_node(name.slice.into(), vec_to_search)
+                .0
             {
-                arg_vec.push(name.clone());
-            } else {
+                let is_same_item: bool = match vec_to_search {
+                    0 => self.current_scope.var_vec[found_idx] == exports.var_vec[idx],
+                    1 => self.current_scope.func_vec[found_idx] == exports.func_vec[idx],
+                    _ => self.current_scope.type_vec[found_idx] == exports.type_vec[idx],
+                };
+
+                if is_same_item {
+                    continue;
+                }
+
+                self.current_token = *name;
                 self.update_error_handler();
-                self.error_handler.throw_arg_alreay_used(name.clone());
+                self.error_handler.throw_name_already_used(vec_to_search);
             }
 
-            self.next(2);
-
-            let val: Either<Either<VarType, ArrayVarType>, FunctionNode<'a>> =
-                Left(self.parse_ty());
+            match vec_to_search {
+                0 => self
+                    .current_scope
+                    .var_vec
+                    .push(exports.var_vec[idx].clone()),
+                1 => self
+                    .current_scope
+                    .func_vec
+                    .push(exports.func_vec[idx].clone()),
+                _ => {
+                    let ty: TypeNode<'a> = exports.type_vec[idx].clone();
 
-            self.next(1);
+                    if !ty.interfaces.is_empty() {
+                        self.impls.insert(ty.name.clone(), ty.interfaces.clone());
+                    }
 
-            TypeArgNode::new(name, val, visibility)
+                    self.current_scope.type_vec.push(ty)
+                }
+            }
         }
-    }
-
-    fn parse_type_init(&mut self) -> InitTypeNode<'a> {
-        let idx: (Result<usize, usize>, bool) =
-            self.search_node(self.current_token.slice.to_owned(), true, 2);
-
-        let found_node: TypeNode = self.current_scope.type_vec[idx.0.unwrap()].clone();
 
-        self.next(2);
+        ImportNode::new(
+            names.into_iter().map(|name| name.slice.into()).collect(),
+            module,
+        )
+    }
 
-        let mut fields: Vec<ValueNode<'a>> = vec![];
-        let mut i: usize = 0;
+    fn parse_export(&mut self) -> ExportNode {
+        self.next(1);
 
-        while self.current_token.token_type != TokenType::CloseBrace {
-            fields.push(
-                self.parse_value(
-                    "".to_owned(),
-                    either::Left(
-                        found_node.args[i]
-                            .clone()
-                            .val
-                            .unwrap_left()
-                            .unwrap_left()
-                            .clone(),
-                    ),
-                ),
-            );
+        let names: Vec<Token<'a>> = self.parse_item_names();
+        let last_token: Token<'a> = self.current_token;
 
-            self.next(if self.peek().token_type == TokenType::Comma {
-                2
-            } else {
-                1
+        for name in &names {
+            let vec_to_search: Option<u8> = (0..3).find(|vec_to_search: &u8| {
+                !self
+                    .current_scope
+                    .search_node(name.slice.into(), *vec_to_search)
+                    .1
             });
 
-            i += 1;
+            if vec_to_search.is_none() {
+                self.current_token = *name;
+                self.update_error_handler();
+                self.error_handler.throw_name_not_defined(3);
+            }
+
+            let idx: usize = self
+                .search_node(name.slice.into(), true, vec_to_search.unwrap())
+                .0
+                .unwrap();
+
+            match vec_to_search.unwrap() {
+                0 => self
+                    .exports
+                    .var_vec
+                    .push(self.current_scope.var_vec[idx].clone()),
+                1 => self
+                    .exports
+                    .func_vec
+                    .push(self.current_scope.func_vec[idx].clone()),
+                _ => self
+                    .exports
+                    .type_vec
+                    .push(self.current_scope.type_vec[idx].clone()),
+            }
         }
 
-        self.next(1);
+        self.current_token = last_token;
 
-        InitTypeNode::new(fields)
+        ExportNode::new(names.into_iter().map(|name| name.slice.into()).collect())
     }
-
-    // TODO: do after if
-    // fn parse_return_if(&mut self) -> ReturnIfNode<'a> {
-    //     todo!()
-    // }
 }
@@ -0,0 +1,407 @@
+use std::{
+    collections::BTreeMap,
+    fs::{create_dir_all, read_dir, read_to_string, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::lexer::{
+    lex,
+    token::{Token, TokenType},
+};
+
+pub const MANIFEST_NAME: &str = "jay.toml";
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Manifest {
+    pub package: Package,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, Dependency>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Package {
+    pub name: String,
+    pub version: String,
+    #[serde(default = "default_entry")]
+    pub entry: String,
+    #[serde(default = "default_src_dirs")]
+    pub src_dirs: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Dependency {
+    pub path: String,
+}
+
+fn default_entry() -> String {
+    "src/main.jay".to_owned()
+}
+
+fn default_src_dirs() -> Vec<String> {
+    vec!["src".to_owned()]
+}
+
+impl Manifest {
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let manifest_path: PathBuf = root.join(MANIFEST_NAME);
+
+        let content: String = read_to_string(&manifest_path)
+            .map_err(|_| format!("could not find {}", manifest_path.display()))?;
+
+        toml::from_str(&content)
+            .map_err(|e| format!("invalid manifest {}: {}", manifest_path.display(), e))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Module {
+    pub name: String,
+    pub path: PathBuf,
+    pub source: String,
+    // import path as written in the source -> resolved module name
+    pub imports: BTreeMap<String, String>,
+}
+
+// A package that's already loaded, `modules` has the full name of each of its modules by
+// their local name
+#[derive(Debug, Clone)]
+struct LoadedPackage {
+    root: PathBuf,
+    entry: String,
+    modules: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Project {
+    pub manifest: Manifest,
+    pub root: PathBuf,
+    pub entry: String,
+    pub modules: Vec<Module>,
+}
+
+impl Project {
+    // * Loading
+
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let manifest: Manifest = Manifest::load(root)?;
+
+        let mut modules: Vec<Module> = vec![];
+        let mut loaded_packages: Vec<LoadedPackage> = vec![];
+        let mut aliases: BTreeMap<String, String> = BTreeMap::new();
+
+        let entry: String = load_package(
+            root,
+            &manifest,
+            None,
+            &mut modules,
+            &mut loaded_packages,
+            &mut aliases,
+        )?;
+
+        // Packages depended on under more than one name are imported through the name they
+        // were loaded with first
+        for module in modules.iter_mut() {
+            for resolved in module.imports.values_mut() {
+                if let Some(name) = aliases.get(resolved) {
+                    *resolved = name.clone();
+                }
+            }
+        }
+
+        let modules: Vec<Module> = sort_modules(modules)?;
+
+        Ok(Self {
+            manifest,
+            root: root.to_path_buf(),
+            entry,
+            modules,
+        })
+    }
+
+    // * Scaffolding
+
+    pub fn create(name: &str) -> Result<PathBuf, String> {
+        let root: PathBuf = PathBuf::from(name);
+
+        if root.exists() {
+            return Err(format!("destination \"{}\" already exists", name));
+        }
+
+        create_dir_all(root.join("src")).map_err(|e| e.to_string())?;
+
+        File::create(root.join(MANIFEST_NAME))
+            .and_then(|mut file| {
+                file.write_all(
+                    format!(
+                        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nentry = \"src/main.jay\"\nsrc_dirs = [\"src\"]\n\n[dependencies]\n",
+                        name
+                    )
+                    .as_bytes(),
+                )
+            })
+            .map_err(|e| e.to_string())?;
+
+        File::create(root.join("src").join("main.jay"))
+            .and_then(|mut file| file.write_all(b"func main(): int {\n    return 0;\n}\n"))
+            .map_err(|e| e.to_string())?;
+
+        Ok(root)
+    }
+}
+
+fn load_package(
+    root: &Path,
+    manifest: &Manifest,
+    prefix: Option<&str>,
+    modules: &mut Vec<Module>,
+    loaded_packages: &mut Vec<LoadedPackage>,
+    aliases: &mut BTreeMap<String, String>,
+) -> Result<String, String> {
+    let canonical_root: PathBuf = root.canonicalize().map_err(|e| e.to_string())?;
+
+    if let Some(loaded) = loaded_packages
+        .iter()
+        .find(|loaded: &&LoadedPackage| loaded.root == canonical_root)
+    {
+        // The root package is never loaded again under a name
+        if let Some(prefix) = prefix {
+            aliases.insert(prefix.to_owned(), loaded.entry.clone());
+
+            for (local_name, name) in &loaded.modules {
+                aliases.insert(format!("{}::{}", prefix, local_name), name.clone());
+            }
+        }
+
+        return Ok(loaded.entry.clone());
+    }
+
+    let entry_path: PathBuf = root.join(&manifest.package.entry);
+
+    if !entry_path.exists() {
+        return Err(format!(
+            "entry point {} of package \"{}\" does not exist",
+            entry_path.display(),
+            manifest.package.name
+        ));
+    }
+
+    let entry_path: PathBuf = entry_path.canonicalize().map_err(|e| e.to_string())?;
+    let mut entry_name: Option<String> = None;
+    let mut local_names: BTreeMap<String, String> = BTreeMap::new();
+    let first_module: usize = modules.len();
+
+    for src_dir in &manifest.package.src_dirs {
+        let src_root: PathBuf = root.join(src_dir);
+
+        if !src_root.is_dir() {
+            return Err(format!(
+                "source directory {} of package \"{}\" does not exist",
+                src_root.display(),
+                manifest.package.name
+            ));
+        }
+
+        let mut files: Vec<PathBuf> = vec![];
+        collect_sources(&src_root, &mut files)?;
+
+        for file in files {
+            let is_entry: bool = file.canonicalize().map_err(|e| e.to_string())? == entry_path;
+            let local_name: String = module_name(&src_root, &file);
+
+            let name: String = match (prefix, is_entry) {
+                (Some(prefix), true) => prefix.to_owned(),
+                (Some(prefix), false) => format!("{}::{}", prefix, local_name),
+                (None, _) => local_name.clone(),
+            };
+
+            if modules.iter().any(|module: &Module| module.name == name) {
+                return Err(format!("module \"{}\" is defined more than once", name));
+            }
+
+            if is_entry {
+                entry_name = Some(name.clone());
+            }
+
+            local_names.insert(local_name, name.clone());
+
+            let source: String =
+                read_to_string(&file).map_err(|_| format!("failed to read {}", file.display()))?;
+
+            let imports: BTreeMap<String, String> = scan_imports(&source)
+                .into_iter()
+                .map(|import: String| (import.clone(), import))
+                .collect();
+
+            modules.push(Module {
+                name,
+                path: file,
+                source,
+                imports,
+            });
+        }
+    }
+
+    let entry_name: String = entry_name.ok_or(format!(
+        "entry point {} is not inside any source directory of package \"{}\"",
+        manifest.package.entry, manifest.package.name
+    ))?;
+
+    // Modules of a dependency import each other by their local names
+    for module in modules[first_module..].iter_mut() {
+        for resolved in module.imports.values_mut() {
+            if let Some(name) = local_names.get(resolved) {
+                *resolved = name.clone();
+            }
+        }
+    }
+
+    loaded_packages.push(LoadedPackage {
+        root: canonical_root,
+        entry: entry_name.clone(),
+        modules: local_names,
+    });
+
+    for (dep_name, dep) in &manifest.dependencies {
+        let dep_root: PathBuf = root.join(&dep.path);
+        let dep_manifest: Manifest = Manifest::load(&dep_root)?;
+
+        load_package(
+            &dep_root,
+            &dep_manifest,
+            Some(dep_name),
+            modules,
+            loaded_packages,
+            aliases,
+        )?;
+    }
+
+    Ok(entry_name)
+}
+
+fn collect_sources(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let mut entries: Vec<PathBuf> = read_dir(dir)
+        .map_err(|e| e.to_string())?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_sources(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "jay") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn module_name(src_root: &Path, file: &Path) -> String {
+    file.strip_prefix(src_root)
+        .unwrap()
+        .with_extension("")
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<String>>()
+        .join("::")
+}
+
+// * Imports
+
+pub fn scan_imports(source: &str) -> Vec<String> {
+    let tokens: Vec<Token> = lex(source);
+
+    let mut imports: Vec<String> = vec![];
+    let mut i: usize = 0;
+
+    while i < tokens.len() {
+        if tokens[i].token_type == TokenType::Import {
+            while i < tokens.len() && tokens[i].token_type != TokenType::From {
+                i += 1;
+            }
+
+            i += 1;
+
+            let mut path: Vec<&str> = vec![];
+
+            while i < tokens.len() && tokens[i].token_type == TokenType::Identifier {
+                path.push(tokens[i].slice);
+
+                if i + 1 < tokens.len() && tokens[i + 1].token_type == TokenType::DoubleColon {
+                    i += 2;
+                } else {
+                    break;
+                }
+            }
+
+            if !path.is_empty() && !imports.contains(&path.join("::")) {
+                imports.push(path.join("::"));
+            }
+        }
+
+        i += 1;
+    }
+
+    imports
+}
+
+// * Ordering
+
+fn sort_modules(modules: Vec<Module>) -> Result<Vec<Module>, String> {
+    let mut sorted: Vec<usize> = vec![];
+    let mut state: Vec<u8> = vec![0; modules.len()];
+
+    for i in 0..modules.len() {
+        visit_module(i, &modules, &mut state, &mut sorted, &mut vec![])?;
+    }
+
+    let mut modules: Vec<Option<Module>> = modules.into_iter().map(Some).collect();
+
+    Ok(sorted
+        .into_iter()
+        .map(|i: usize| modules[i].take().unwrap())
+        .collect())
+}
+
+// state: 0 = not visited, 1 = visiting, 2 = done
+fn visit_module(
+    i: usize,
+    modules: &[Module],
+    state: &mut Vec<u8>,
+    sorted: &mut Vec<usize>,
+    stack: &mut Vec<String>,
+) -> Result<(), String> {
+    match state[i] {
+        2 => return Ok(()),
+        1 => {
+            stack.push(modules[i].name.clone());
+            return Err(format!("import cycle detected: {}", stack.join(" -> ")));
+        }
+        _ => {}
+    }
+
+    state[i] = 1;
+    stack.push(modules[i].name.clone());
+
+    for import in modules[i].imports.values() {
+        let dep: usize = modules
+            .iter()
+            .position(|module: &Module| &module.name == import)
+            .ok_or(format!(
+                "cannot find module \"{}\" imported by \"{}\"",
+                import, modules[i].name
+            ))?;
+
+        visit_module(dep, modules, state, sorted, stack)?;
+    }
+
+    stack.pop();
+    state[i] = 2;
+    sorted.push(i);
+
+    Ok(())
+}
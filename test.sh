@@ -6,4 +6,29 @@ cargo run ./test/type_test.jay &&
 cargo run ./test/un_bin_op_test.jay &&
 cargo run ./test/var_let_const_test.jay &&
 cargo run ./test/i_e_test.jay &&
-cargo run ./test/main_test.jay
\ No newline at end of file
+cargo run ./test/main_test.jay &&
+cargo run ./test/dyn_array_test.jay &&
+cargo run ./test/multi_array_test.jay &&
+cargo run ./test/arr_index_test.jay &&
+cargo run ./test/arr_init_test.jay &&
+cargo run ./test/infer_test.jay &&
+cargo run ./test/check_test.jay &&
+cargo run ./test/call_test.jay &&
+cargo run ./test/forward_test.jay &&
+cargo run ./test/return_test.jay &&
+cargo run ./test/exit_if_test.jay &&
+cargo run ./test/sized_test.jay &&
+cargo run ./test/enum_test.jay &&
+cargo run ./test/generic_test.jay &&
+cargo run ./test/lambda_test.jay &&
+cargo run ./test/variadic_test.jay &&
+cargo run ./test/named_args_test.jay &&
+cargo run ./test/pipe_test.jay &&
+cargo run ./test/for_test.jay &&
+cargo run ./test/loop_control_test.jay &&
+cargo run ./test/tuple_test.jay &&
+cargo run ./test/map_test.jay &&
+cargo run ./test/string_test.jay &&
+cargo run ./test/interface_test.jay &&
+cargo run ./test/operator_test.jay &&
+(cd ./test/project_test && cargo run build)
\ No newline at end of file